@@ -0,0 +1,25 @@
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bakes the git revision and build time into the binary as
+/// `BURNER_GIT_HASH` and `BURNER_BUILD_TIMESTAMP` (Unix seconds), read by
+/// `utils::build_info`.
+fn main() {
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .unwrap_or_default();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    println!("cargo:rustc-env=BURNER_GIT_HASH={}", git_hash);
+    println!("cargo:rustc-env=BURNER_BUILD_TIMESTAMP={}", timestamp);
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+}
@@ -0,0 +1,170 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use crate::utils::{fetch_json, fetch_response, AppError};
+
+const STATUS_PATH: &str = "/api/status";
+const HEALTH_PATH: &str = "/health";
+const STATS_PATH: &str = "/api/stats";
+const TOKEN_PATH: &str = "/api/token";
+
+/// `<meta name="api-base-url" content="...">` in `index.html` points the app at
+/// a backend on another origin at runtime.
+const BASE_URL_META: &str = "api-base-url";
+/// Baked in at build time when no meta tag is present.
+const BUILD_BASE_URL: Option<&str> = option_env!("BURNER_API_BASE_URL");
+
+/// Absolute or same-origin URL for a backend `path` such as `/api/status`.
+/// Without a configured base the path is used as is, i.e. same-origin.
+pub fn api_url(path: &str) -> String {
+    let base = meta_base_url()
+        .or_else(|| BUILD_BASE_URL.map(str::to_string))
+        .unwrap_or_default();
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+fn meta_base_url() -> Option<String> {
+    let document = web_sys::window()?.document()?;
+    let meta = document
+        .query_selector(&format!(r#"meta[name="{}"]"#, BASE_URL_META))
+        .ok()??;
+    meta.get_attribute("content").filter(|content| !content.trim().is_empty())
+}
+
+/// GETs an optional backend feature at `path`. Not every deployment serves
+/// every route (the Fastify server has none of status, stats or health), so
+/// a 404 is the feature being absent, `Ok(None)`, rather than the backend
+/// being down.
+async fn fetch_feature<T: DeserializeOwned>(path: &str) -> Result<Option<T>, AppError> {
+    match fetch_json(&api_url(path), "GET", None).await {
+        Ok(value) => Ok(Some(value)),
+        Err(AppError::Http(404)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Operator-controlled state served by the backend.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ServiceStatus {
+    pub maintenance: bool,
+    #[serde(default)]
+    pub message: String,
+    /// Whether the backend answered its health check. Burning talks to the
+    /// RPC directly and works either way; only server features pause.
+    #[serde(skip, default = "reachable")]
+    pub backend_available: bool,
+}
+
+impl Default for ServiceStatus {
+    fn default() -> Self {
+        Self {
+            maintenance: false,
+            message: String::new(),
+            backend_available: reachable(),
+        }
+    }
+}
+
+/// Assumed until a health check says otherwise.
+fn reachable() -> bool {
+    true
+}
+
+/// `None` when the backend has no status endpoint, i.e. no maintenance.
+pub async fn fetch_status() -> Result<Option<ServiceStatus>, AppError> {
+    fetch_feature(STATUS_PATH).await
+}
+
+/// All-time totals the backend aggregates from the burns it has recorded.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct BurnStats {
+    pub burns: u64,
+    /// Estimated USD value of everything burned, when the backend prices it.
+    #[serde(default)]
+    pub value_usd: Option<f64>,
+}
+
+impl BurnStats {
+    /// Footer line like "1234 burns through this app (~$5678.90)".
+    pub fn summary(&self) -> String {
+        let noun = if self.burns == 1 { "burn" } else { "burns" };
+        match self.value_usd {
+            Some(value) => format!("{} {} through this app (~${:.2})", self.burns, noun, value),
+            None => format!("{} {} through this app", self.burns, noun),
+        }
+    }
+}
+
+/// `None` when the backend doesn't aggregate stats.
+pub async fn fetch_stats() -> Result<Option<BurnStats>, AppError> {
+    fetch_feature(STATS_PATH).await
+}
+
+/// A mainnet token's metadata as resolved by the backend, which talks to the
+/// chain and metadata program so the browser avoids CORS and rate limits.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+    #[serde(default)]
+    pub logo: Option<String>,
+}
+
+/// Metadata for `mint`; `None` when the backend doesn't know the mint (404).
+/// A backend without the endpoint reads the same way.
+pub async fn fetch_token_metadata(mint: &str) -> Result<Option<TokenMetadata>, AppError> {
+    fetch_feature(&format!("{}/{}", TOKEN_PATH, mint)).await
+}
+
+/// Whether the backend answers `/health` with a 2xx. The body isn't read, so
+/// a plain `ok` or an empty 204 counts as healthy. A 404 comes from a
+/// backend that's up but has no health route, so it counts too.
+pub async fn check_health() -> bool {
+    match fetch_response(&api_url(HEALTH_PATH), "GET", None).await {
+        Ok(_) | Err(AppError::Http(404)) => true,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maintenance_status_is_read_from_the_backend() {
+        let status: ServiceStatus =
+            serde_json::from_str(r#"{"maintenance":true,"message":"RPC issues, back soon"}"#).unwrap();
+        assert!(status.maintenance);
+        assert_eq!(status.message, "RPC issues, back soon");
+        // Reachability comes from the health check, never the status body.
+        assert!(status.backend_available);
+    }
+
+    #[test]
+    fn normal_status_needs_no_message() {
+        let status: ServiceStatus = serde_json::from_str(r#"{"maintenance":false}"#).unwrap();
+        assert_eq!(status, ServiceStatus::default());
+    }
+
+    #[test]
+    fn unreachable_backend_means_no_maintenance() {
+        let status = ServiceStatus::default();
+        assert!(!status.maintenance);
+        assert!(status.message.is_empty());
+    }
+
+    #[test]
+    fn stats_are_read_with_or_without_a_value() {
+        let priced: BurnStats = serde_json::from_str(r#"{"burns":1234,"value_usd":5678.9}"#).unwrap();
+        assert_eq!(priced, BurnStats { burns: 1234, value_usd: Some(5678.9) });
+        let unpriced: BurnStats = serde_json::from_str(r#"{"burns":0}"#).unwrap();
+        assert_eq!(unpriced, BurnStats { burns: 0, value_usd: None });
+    }
+
+    #[test]
+    fn stats_summary_counts_burns_and_value() {
+        assert_eq!(BurnStats { burns: 1234, value_usd: Some(5678.9) }.summary(), "1234 burns through this app (~$5678.90)");
+        assert_eq!(BurnStats { burns: 1, value_usd: None }.summary(), "1 burn through this app");
+        assert_eq!(BurnStats { burns: 0, value_usd: Some(0.0) }.summary(), "0 burns through this app (~$0.00)");
+    }
+}
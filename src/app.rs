@@ -1,44 +1,470 @@
 use yew::prelude::*;
-use crate::components::{wallet::WalletConnect, burn_form::BurnForm};
+use web_sys::HtmlSelectElement;
+use wasm_bindgen::JsCast;
+use crate::components::{
+    banner::{Banner, Severity},
+    burn_form::{BurnForm, InterruptedBurn},
+    dust_scanner::DustScanner,
+    error_boundary::ErrorBoundary,
+    history::{BurnRecord, History},
+    onboarding::Onboarding,
+    settings::SettingsPanel,
+    wallet::WalletConnect,
+};
+use crate::context::{DecimalsCache, InFlightBurns, Settings, WalletContext};
+use crate::api::{self, BurnStats, ServiceStatus};
+use crate::interop;
+use crate::rpc;
+use crate::storage;
+use crate::transaction;
+use crate::utils::{build_info, Cluster, Explorer};
+
+/// Host the official deployment is served from, baked in at build time. When
+/// unset, the current host is shown without a comparison.
+const EXPECTED_HOST: Option<&str> = option_env!("BURNER_EXPECTED_HOST");
+
+/// Name of the chosen block explorer; most other settings last a session.
+const EXPLORER_KEY: &str = "explorer";
+
+/// Whether the compact layout is on.
+const COMPACT_KEY: &str = "compact";
+
+/// Name of the last selected cluster, so testers stay on devnet between visits.
+const CLUSTER_KEY: &str = "cluster";
+
+/// A burn sent before the page last went away, polled for again on load.
+struct ResumedBurn {
+    record: BurnRecord,
+    display_amount: String,
+    /// `None` while still checking.
+    outcome: Option<Result<(), String>>,
+}
 
 pub struct App {
-    wallet_connected: bool,
+    wallet: WalletContext,
+    wallet_seen: bool,
+    cluster: Cluster,
+    /// The cluster the connected wallet reports, where it reports one.
+    wallet_cluster: Option<Cluster>,
+    decimals: DecimalsCache,
+    history: Vec<BurnRecord>,
+    settings: Settings,
+    status: ServiceStatus,
+    /// All-time totals for the footer; `None` until (or unless) the backend
+    /// serves them.
+    stats: Option<BurnStats>,
+    /// Mints being burned right now, watched by the form and the scanner.
+    burns_in_flight: InFlightBurns,
+    /// Seconds until a rate-limited RPC request is retried.
+    rate_limited: Option<u32>,
+    resumed: Option<ResumedBurn>,
 }
 
 pub enum Msg {
-    WalletConnected(bool),
+    WalletConnected(bool, Option<String>),
+    SelectCluster(Cluster),
+    Burned(BurnRecord),
+    EndpointChanged,
+    RateLimited(Option<u32>),
+    StatusLoaded(ServiceStatus),
+    BackendChecked(bool),
+    StatsLoaded(BurnStats),
+    UpdateSettings(Settings),
+    ResumeSettled(Result<(), String>),
+    DismissResumed,
 }
 
 impl Component for App {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self {
-            wallet_connected: false,
-        }
+    fn create(ctx: &Context<Self>) -> Self {
+        rpc::on_endpoint_change(ctx.link().callback(|_| Msg::EndpointChanged));
+        rpc::on_rate_limit(ctx.link().callback(Msg::RateLimited));
+
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let available = api::check_health().await;
+            link.send_message(Msg::BackendChecked(available));
+            // A missing or unreachable status endpoint means business as usual.
+            if let Ok(Some(status)) = api::fetch_status().await {
+                link.send_message(Msg::StatusLoaded(status));
+            }
+            if available {
+                if let Ok(Some(stats)) = api::fetch_stats().await {
+                    link.send_message(Msg::StatsLoaded(stats));
+                }
+            }
+        });
+
+        let cluster = storage::get::<String>(CLUSTER_KEY)
+            .ok()
+            .flatten()
+            .and_then(|name| Cluster::from_name(&name))
+            .unwrap_or_default();
+
+        let mut app = Self {
+            wallet: WalletContext::default(),
+            wallet_seen: false,
+            cluster,
+            wallet_cluster: None,
+            decimals: DecimalsCache::default().for_cluster(cluster),
+            history: Vec::new(),
+            settings: Settings {
+                explorer: storage::get::<String>(EXPLORER_KEY)
+                    .ok()
+                    .flatten()
+                    .and_then(|name| Explorer::from_name(&name))
+                    .unwrap_or_default(),
+                compact: storage::get::<bool>(COMPACT_KEY).ok().flatten().unwrap_or(false),
+                ..Settings::default()
+            },
+            status: ServiceStatus::default(),
+            stats: None,
+            burns_in_flight: InFlightBurns::default(),
+            rate_limited: None,
+            resumed: None,
+        };
+        app.resume_interrupted(ctx);
+        app
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::WalletConnected(status) => {
-                self.wallet_connected = status;
+            Msg::WalletConnected(connected, public_key) => {
+                self.wallet = WalletContext { connected, public_key };
+                self.wallet_cluster = if connected { interop::wallet_cluster() } else { None };
+                self.wallet_seen |= connected;
+                true
+            }
+            Msg::SelectCluster(cluster) => {
+                let _ = storage::set(CLUSTER_KEY, &cluster.name());
+                self.cluster = cluster;
+                self.decimals = self.decimals.for_cluster(cluster);
+                true
+            }
+            Msg::Burned(record) => {
+                self.history.push(record);
+                true
+            }
+            Msg::EndpointChanged => true,
+            Msg::RateLimited(wait) => {
+                self.rate_limited = wait;
+                true
+            }
+            Msg::StatusLoaded(status) => {
+                self.status = ServiceStatus {
+                    backend_available: self.status.backend_available,
+                    ..status
+                };
+                true
+            }
+            Msg::BackendChecked(available) => {
+                if available == self.status.backend_available {
+                    return false;
+                }
+                self.status.backend_available = available;
+                true
+            }
+            Msg::StatsLoaded(stats) => {
+                self.stats = Some(stats);
+                true
+            }
+            Msg::UpdateSettings(settings) => {
+                if settings.custom_rpc_url != self.settings.custom_rpc_url {
+                    let url = Some(settings.custom_rpc_url.clone()).filter(|url| !url.is_empty());
+                    rpc::set_custom_endpoint(url);
+                }
+                if settings.explorer != self.settings.explorer {
+                    let _ = storage::set(EXPLORER_KEY, &settings.explorer.name());
+                }
+                if settings.compact != self.settings.compact {
+                    let _ = storage::set(COMPACT_KEY, &settings.compact);
+                }
+                self.settings = settings;
+                true
+            }
+            Msg::ResumeSettled(outcome) => self.resume_settled(outcome),
+            Msg::DismissResumed => {
+                self.resumed = None;
                 true
             }
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let wallet_callback = ctx.link().callback(Msg::WalletConnected);
-        
+        let on_burn = ctx.link().callback(Msg::Burned);
+        let on_settings = ctx.link().callback(Msg::UpdateSettings);
+        let wallet_callback = ctx
+            .link()
+            .callback(|(connected, key)| Msg::WalletConnected(connected, key));
+        let cluster_mismatch = self.wallet_cluster.filter(|cluster| *cluster != self.cluster);
+        let onchange = ctx.link().batch_callback(|e: Event| {
+            let select: HtmlSelectElement = e.target().unwrap().dyn_into().unwrap();
+            Cluster::from_name(&select.value()).map(Msg::SelectCluster)
+        });
+
         html! {
-            <div class="container">
-                <h1>{"Solana Token Burner"}</h1>
-                <WalletConnect on_connect={wallet_callback.clone()} />
-                if self.wallet_connected {
-                    <BurnForm />
-                }
-            </div>
+            <ContextProvider<WalletContext> context={self.wallet.clone()}>
+                <ContextProvider<DecimalsCache> context={self.decimals.clone()}>
+                    <ContextProvider<Settings> context={self.settings.clone()}>
+                        <ContextProvider<ServiceStatus> context={self.status.clone()}>
+                            <ContextProvider<InFlightBurns> context={self.burns_in_flight.clone()}>
+                                <div class={classes!("container", self.settings.compact.then_some("compact"))}>
+                                    <h1>{"Solana Token Burner"}</h1>
+                                    <Onboarding />
+                                    {origin_notice()}
+                                    if self.status.maintenance {
+                                        <Banner severity={Severity::Error}>
+                                            <strong>{"Burning is temporarily disabled. "}</strong>
+                                            {self.status.message.clone()}
+                                        </Banner>
+                                    }
+                                    if let Some(wallet_cluster) = cluster_mismatch {
+                                        <Banner severity={Severity::Warning}>
+                                            {format!(
+                                                "Your wallet is on {} but the app is on {}. Burns are sent to the app's cluster, so tokens and balances won't match what your wallet shows. ",
+                                                wallet_cluster.name(),
+                                                self.cluster.name()
+                                            )}
+                                            <button
+                                                type="button"
+                                                class="secondary-button"
+                                                onclick={ctx.link().callback(move |_| Msg::SelectCluster(wallet_cluster))}
+                                            >
+                                                {format!("Switch app to {}", wallet_cluster.name())}
+                                            </button>
+                                        </Banner>
+                                    }
+                                    {self.resumed_notice(ctx)}
+                                    <select class="cluster-select" {onchange}>
+                                        { for Cluster::ALL.iter().map(|cluster| html! {
+                                            <option value={cluster.name()} selected={*cluster == self.cluster}>
+                                                {cluster.name()}
+                                            </option>
+                                        }) }
+                                    </select>
+                                    <div class="rpc-endpoint">
+                                        {format!("RPC: {}", rpc::active_endpoint(self.cluster))}
+                                        if let Some(wait) = self.rate_limited {
+                                            <span class="rate-limited">{format!(" — Rate limited, retrying in {}s", wait)}</span>
+                                        }
+                                        if !self.status.backend_available {
+                                            <span
+                                                class="backend-degraded"
+                                                title="Burning works directly over RPC. Maintenance notices and usage stats are paused."
+                                            >
+                                                {" — Server unreachable, limited mode"}
+                                            </span>
+                                        }
+                                    </div>
+                                    <SettingsPanel settings={self.settings.clone()} on_change={on_settings} />
+                                    <ErrorBoundary>
+                                        <WalletConnect on_connect={wallet_callback.clone()} cluster={self.cluster} />
+                                        if self.wallet.connected || self.wallet_seen {
+                                            <BurnForm on_burn={on_burn.clone()} />
+                                        }
+                                        <DustScanner {on_burn} />
+                                        if self.wallet.connected || self.wallet_seen {
+                                            <History records={self.history.clone()} />
+                                        }
+                                    </ErrorBoundary>
+                                    <footer class="build-info">
+                                        if let (true, Some(stats)) = (self.status.backend_available, &self.stats) {
+                                            <div class="burn-stats">{stats.summary()}</div>
+                                        }
+                                        {build_info().to_string()}
+                                    </footer>
+                                </div>
+                            </ContextProvider<InFlightBurns>>
+                        </ContextProvider<ServiceStatus>>
+                    </ContextProvider<Settings>>
+                </ContextProvider<DecimalsCache>>
+            </ContextProvider<WalletContext>>
+        }
+    }
+}
+
+impl App {
+    /// Picks up a burn that was sent but not yet seen confirming when the
+    /// page went away. Only the signature and RPC are needed, so this runs
+    /// whether or not the wallet reconnects.
+    fn resume_interrupted(&mut self, ctx: &Context<Self>) {
+        let record = match self.begin_resume() {
+            Some(record) => record,
+            None => return,
+        };
+        let settings = self.settings.clone();
+        let cluster = record.cluster;
+        let signature = record.signature;
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let outcome = transaction::confirm_signature(
+                cluster,
+                &signature,
+                settings.confirm_poll_interval_ms,
+                settings.confirm_max_attempts,
+                settings.slow_confirm_seconds,
+                || {},
+            )
+            .await;
+            link.send_message(Msg::ResumeSettled(outcome));
+        });
+    }
+
+    /// Picks up the stored interrupted burn, if any, and marks its mint as in
+    /// flight. Returns the record to poll for.
+    fn begin_resume(&mut self) -> Option<BurnRecord> {
+        let (record, display_amount) = InterruptedBurn::load()?;
+        // Keeps the form and the scanner from burning the mint again meanwhile.
+        self.burns_in_flight.start(&record.mint);
+        self.resumed = Some(ResumedBurn { record: record.clone(), display_amount, outcome: None });
+        Some(record)
+    }
+
+    /// Settles a resumed burn: it's forgotten either way, and only goes into
+    /// history once confirmed.
+    fn resume_settled(&mut self, outcome: Result<(), String>) -> bool {
+        let resumed = match &mut self.resumed {
+            Some(resumed) => resumed,
+            None => return false,
+        };
+        InterruptedBurn::clear();
+        self.burns_in_flight.finish(&resumed.record.mint);
+        if outcome.is_ok() {
+            self.history.push(resumed.record.clone());
         }
+        resumed.outcome = Some(outcome);
+        true
+    }
+
+    fn resumed_notice(&self, ctx: &Context<Self>) -> Html {
+        let resumed = match &self.resumed {
+            Some(resumed) => resumed,
+            None => return html! {},
+        };
+        let explorer = html! {
+            <a href={resumed.record.explorer_url(self.settings.explorer)} target="_blank" rel="noopener noreferrer">
+                {"View on explorer"}
+            </a>
+        };
+        let on_dismiss = ctx.link().callback(|_| Msg::DismissResumed);
+
+        match &resumed.outcome {
+            None => html! {
+                <div class="status-message pending" role="status">
+                    {format!("Checking a previous burn of {}... ", resumed.display_amount)}
+                    {explorer}
+                </div>
+            },
+            Some(Ok(())) => html! {
+                <Banner severity={Severity::Success} {on_dismiss}>
+                    {format!("Your previous burn of {} was confirmed. ", resumed.display_amount)}
+                    {explorer}
+                </Banner>
+            },
+            Some(Err(error)) => html! {
+                <Banner severity={Severity::Warning} {on_dismiss}>
+                    {format!("{} (your previous burn of {}). ", error, resumed.display_amount)}
+                    {explorer}
+                </Banner>
+            },
+        }
+    }
+}
+
+/// Shows the host the page is served from so users can spot a phishing clone.
+fn origin_notice() -> Html {
+    let host = web_sys::window()
+        .and_then(|window| window.location().host().ok())
+        .unwrap_or_default();
+    let mismatch = EXPECTED_HOST.map_or(false, |expected| !expected.eq_ignore_ascii_case(&host));
+
+    html! {
+        <div class={if mismatch { "origin-notice error" } else { "origin-notice" }}>
+            {"🔒 "}<strong>{host}</strong>
+            if mismatch {
+                <div>
+                    {format!(
+                        "Warning: this is not {}. You may be on a copy of the burner — don't sign anything.",
+                        EXPECTED_HOST.unwrap_or_default()
+                    )}
+                </div>
+            }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const SIGNATURE: &str =
+        "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+
+    fn app() -> App {
+        App {
+            wallet: WalletContext::default(),
+            wallet_seen: false,
+            cluster: Cluster::Mainnet,
+            wallet_cluster: None,
+            decimals: DecimalsCache::default(),
+            history: Vec::new(),
+            settings: Settings::default(),
+            status: ServiceStatus::default(),
+            stats: None,
+            burns_in_flight: InFlightBurns::default(),
+            rate_limited: None,
+            resumed: None,
+        }
+    }
+
+    fn interrupt() -> BurnRecord {
+        let record = BurnRecord {
+            timestamp: 1_700_000_000_000.0,
+            mint: MINT.to_string(),
+            symbol: None,
+            amount: 2.0,
+            signature: SIGNATURE.to_string(),
+            cluster: Cluster::Devnet,
+            memo: None,
+        };
+        InterruptedBurn::save(&record, "2");
+        record
+    }
+
+    #[test]
+    fn nothing_to_resume_without_a_stored_burn() {
+        let mut app = app();
+        assert_eq!(app.begin_resume(), None);
+        assert!(app.resumed.is_none());
+        assert!(!app.resume_settled(Ok(())));
+    }
+
+    #[test]
+    fn resuming_holds_the_mint_until_settled() {
+        let mut app = app();
+        let record = interrupt();
+        assert_eq!(app.begin_resume(), Some(record.clone()));
+        assert!(app.burns_in_flight.contains(MINT));
+        assert_eq!(app.resumed.as_ref().map(|resumed| resumed.display_amount.as_str()), Some("2"));
+
+        assert!(app.resume_settled(Ok(())));
+        assert!(!app.burns_in_flight.contains(MINT));
+        assert_eq!(app.history, vec![record]);
+        assert_eq!(InterruptedBurn::load(), None);
+    }
+
+    #[test]
+    fn unconfirmed_resumed_burns_stay_out_of_history() {
+        let mut app = app();
+        interrupt();
+        app.begin_resume();
+        assert!(app.resume_settled(Err("Burn transaction failed on-chain".to_string())));
+        assert!(app.history.is_empty());
+        assert!(!app.burns_in_flight.contains(MINT));
+        assert_eq!(InterruptedBurn::load(), None);
+        assert!(matches!(app.resumed.map(|resumed| resumed.outcome), Some(Some(Err(_)))));
     }
 }
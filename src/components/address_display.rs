@@ -0,0 +1,108 @@
+use yew::prelude::*;
+use crate::context::Settings;
+use crate::interop;
+use crate::utils::{abbreviate, explorer_address_url, explorer_url, Cluster, Explorer};
+
+/// What an [`AddressDisplay`] shows, which decides where its link goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressKind {
+    Wallet,
+    Mint,
+    Signature,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub address: AttrValue,
+    pub kind: AddressKind,
+    #[prop_or_default]
+    pub cluster: Cluster,
+}
+
+/// An abbreviated address or signature linking to the explorer, with a button
+/// to copy the full value.
+pub struct AddressDisplay {
+    copy_status: Option<&'static str>,
+    explorer: Explorer,
+    _settings_handle: Option<ContextHandle<Settings>>,
+}
+
+pub enum Msg {
+    Copy,
+    /// The clipboard answered for this address.
+    Copied(AttrValue, bool),
+    SettingsChanged(Settings),
+}
+
+impl Component for AddressDisplay {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (settings, settings_handle) = match ctx
+            .link()
+            .context::<Settings>(ctx.link().callback(Msg::SettingsChanged))
+        {
+            Some((settings, handle)) => (settings, Some(handle)),
+            None => (Settings::default(), None),
+        };
+
+        Self {
+            copy_status: None,
+            explorer: settings.explorer,
+            _settings_handle: settings_handle,
+        }
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.copy_status = None;
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Copy => {
+                let address = ctx.props().address.clone();
+                let link = ctx.link().clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let copied = interop::copy_to_clipboard(&address).await.is_ok();
+                    link.send_message(Msg::Copied(address, copied));
+                });
+                false
+            }
+            Msg::Copied(address, copied) => {
+                // Props moved on to another address meanwhile.
+                if address != ctx.props().address {
+                    return false;
+                }
+                self.copy_status = Some(if copied { "Copied" } else { "Copy failed" });
+                true
+            }
+            Msg::SettingsChanged(settings) => {
+                let changed = settings.explorer != self.explorer;
+                self.explorer = settings.explorer;
+                changed
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props { address, kind, cluster } = ctx.props();
+        let href = match kind {
+            AddressKind::Signature => explorer_url(address, *cluster, self.explorer),
+            AddressKind::Wallet | AddressKind::Mint => explorer_address_url(address, *cluster, self.explorer),
+        };
+        let oncopy = ctx.link().callback(|_| Msg::Copy);
+
+        html! {
+            <span class="address-display" title={address.clone()}>
+                <a {href} target="_blank" rel="noopener noreferrer">
+                    <code>{abbreviate(address)}</code>
+                </a>
+                <button type="button" class="address-copy" onclick={oncopy}>
+                    {self.copy_status.unwrap_or("Copy")}
+                </button>
+            </span>
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use yew::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+    Success,
+}
+
+impl Severity {
+    fn class(self) -> &'static str {
+        match self {
+            Severity::Info => "banner info",
+            Severity::Warning => "banner warning",
+            Severity::Error => "banner error",
+            Severity::Success => "banner success",
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub severity: Severity,
+    /// Shows a close button; the banner hides itself and then calls this.
+    #[prop_or_default]
+    pub on_dismiss: Option<Callback<()>>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// A full-width notice. Errors and warnings are announced as alerts, info and
+/// success politely.
+pub struct Banner {
+    dismissed: bool,
+}
+
+impl Component for Banner {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { dismissed: false }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        self.dismissed = true;
+        if let Some(on_dismiss) = &ctx.props().on_dismiss {
+            on_dismiss.emit(());
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        // A new message in the same place hasn't been dismissed yet.
+        let props = ctx.props();
+        if props.children != old_props.children || props.severity != old_props.severity {
+            self.dismissed = false;
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.dismissed {
+            return html! {};
+        }
+        let props = ctx.props();
+        let role = match props.severity {
+            Severity::Error | Severity::Warning => "alert",
+            Severity::Info | Severity::Success => "status",
+        };
+
+        html! {
+            <div class={props.severity.class()} {role}>
+                <div class="banner-content">{props.children.clone()}</div>
+                if props.on_dismiss.is_some() {
+                    <button
+                        type="button"
+                        class="banner-dismiss"
+                        aria-label="Dismiss"
+                        onclick={ctx.link().callback(|_| ())}
+                    >
+                        {"×"}
+                    </button>
+                }
+            </div>
+        }
+    }
+}
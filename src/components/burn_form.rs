@@ -1,122 +1,1156 @@
 use yew::prelude::*;
-use web_sys::HtmlInputElement;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use wasm_bindgen::JsCast;
-use js_sys::{Object, Reflect};
-use wasm_bindgen::prelude::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use gloo_timers::callback::Timeout;
+use serde::{Deserialize, Serialize};
+use crate::api::{self, ServiceStatus, TokenMetadata};
+use crate::components::address_display::{AddressDisplay, AddressKind};
+use crate::components::banner::{Banner, Severity};
+use crate::components::confirm_challenge::ConfirmChallenge;
+use crate::components::history::BurnRecord;
+use crate::components::modal::Modal;
+use crate::components::token_logo::TokenLogo;
+use crate::components::tooltip::Tooltip;
+use crate::context::{DecimalsCache, InFlightBurns, Settings, WalletContext};
+use crate::interop;
+use crate::rpc::{self, TokenAccount};
+use crate::storage;
+use crate::telemetry::{self, Event};
+use crate::transaction::{self, BurnRequest, TokenProgram};
+use crate::utils::{
+    abbreviate, amount_diverges, amount_to_base_units, base_units_to_decimal_str, format_amount, is_near_full_supply,
+    is_valid_address, is_valid_mint, is_valid_signature, lamports_to_sol, limit_decimal_places, sanitize_memo,
+    Cluster, NumberSeparators, PriorityFee, BASE_FEE_LAMPORTS, DEFAULT_COMPUTE_UNIT_LIMIT, MAX_CUSTOM_PRIORITY_FEE,
+    MAX_MEMO_CHARS,
+};
+use crate::validation::{self, BurnCheck};
+
+const BASE_UNITS_KEY: &str = "show_base_units";
+
+const COMPRESSED_NFT_MESSAGE: &str =
+    "This is a compressed NFT. It has no token account to burn from; burn it with an NFT burner that supports Bubblegum.";
+
+/// Largest decimals value accepted when it has to be entered by hand.
+const MAX_DECIMALS: u8 = 18;
+
+/// Phrase typed to confirm burns above the challenge threshold.
+const CHALLENGE_PHRASE: &str = "BURN";
+
+/// A validated burn waiting for the user to confirm it.
+#[derive(Clone, Debug, PartialEq)]
+struct PendingBurn {
+    request: BurnRequest,
+    amount: f64,
+    display_amount: String,
+    requires_challenge: bool,
+    /// Burns the whole balance (from Max), which always needs the challenge.
+    entire_balance: bool,
+    /// Burns most or all of the mint's total supply.
+    near_full_supply: bool,
+}
+
+/// A burn that confirmed on-chain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BurnSuccess {
+    pub signature: String,
+    /// Amount as the user entered it, in UI units.
+    pub amount: String,
+    pub mint: String,
+}
+
+/// Where a confirmed burn is on its way on-chain.
+#[derive(Clone, Debug, Default, PartialEq)]
+enum BurnPhase {
+    #[default]
+    Idle,
+    /// Waiting for the wallet to sign and send.
+    Signing,
+    /// Sent; polling for confirmation.
+    Confirming(BurnRecord),
+    /// Still polling past the slow-confirmation threshold.
+    StillConfirming(BurnRecord),
+}
+
+/// Longest a refresh spinner turns before giving up on slow answers.
+const REFRESH_TIMEOUT_MS: u32 = 10_000;
+
+/// Key of the burn awaiting confirmation, if any; see [`InterruptedBurn`].
+const INTERRUPTED_BURN_KEY: &str = "interrupted_burn";
+
+/// A sent burn whose confirmation hasn't been observed yet. It's stored from
+/// the moment the wallet returns a signature until polling gives an answer,
+/// so a reload in between picks the polling back up instead of losing track
+/// of a burn the user never saw land. The app does that on load, whether or
+/// not a wallet connects.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct InterruptedBurn {
+    signature: String,
+    mint: String,
+    amount: f64,
+    display_amount: String,
+    cluster: String,
+    timestamp: f64,
+    #[serde(default)]
+    memo: Option<String>,
+}
+
+impl InterruptedBurn {
+    pub(crate) fn save(record: &BurnRecord, display_amount: &str) {
+        let interrupted = InterruptedBurn {
+            signature: record.signature.clone(),
+            mint: record.mint.clone(),
+            amount: record.amount,
+            display_amount: display_amount.to_string(),
+            cluster: record.cluster.name().to_string(),
+            timestamp: record.timestamp,
+            memo: record.memo.clone(),
+        };
+        if let Err(err) = storage::set(INTERRUPTED_BURN_KEY, &interrupted) {
+            log::warn!("Could not remember the pending burn: {}", err);
+        }
+    }
+
+    /// The stored burn as a history record plus its display amount. Anything
+    /// unreadable is dropped rather than polled for.
+    pub(crate) fn load() -> Option<(BurnRecord, String)> {
+        let stored = storage::get::<InterruptedBurn>(INTERRUPTED_BURN_KEY).ok().flatten();
+        let interrupted = match stored {
+            Some(interrupted) if is_valid_signature(&interrupted.signature) => interrupted,
+            Some(_) | None => {
+                Self::clear();
+                return None;
+            }
+        };
+        let cluster = match Cluster::from_name(&interrupted.cluster) {
+            Some(cluster) => cluster,
+            None => {
+                Self::clear();
+                return None;
+            }
+        };
+        let record = BurnRecord {
+            timestamp: interrupted.timestamp,
+            mint: interrupted.mint,
+            symbol: None,
+            amount: interrupted.amount,
+            signature: interrupted.signature,
+            cluster,
+            memo: interrupted.memo,
+        };
+        Some((record, interrupted.display_amount))
+    }
+
+    pub(crate) fn clear() {
+        storage::remove(INTERRUPTED_BURN_KEY);
+    }
+}
+
+/// Confirmation polling settings captured when a burn starts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Polling {
+    interval_ms: u32,
+    max_attempts: u32,
+    slow_after_secs: u32,
+}
+
+impl From<&Settings> for Polling {
+    fn from(settings: &Settings) -> Self {
+        Self {
+            interval_ms: settings.confirm_poll_interval_ms,
+            max_attempts: settings.confirm_max_attempts,
+            slow_after_secs: settings.slow_confirm_seconds,
+        }
+    }
+}
+
+/// Polls a sent burn to an answer and reports it. The burn stays stored as
+/// interrupted until polling gives up or settles, whatever the form does
+/// meanwhile.
+async fn follow_burn(
+    task: BurnTask,
+    polling: Polling,
+    record: BurnRecord,
+    display_amount: String,
+    on_burn: Callback<BurnRecord>,
+    burns: InFlightBurns,
+) {
+    InterruptedBurn::save(&record, &display_amount);
+    let slow_task = task.clone();
+    let confirmed = transaction::confirm_signature(
+        record.cluster,
+        &record.signature,
+        polling.interval_ms,
+        polling.max_attempts,
+        polling.slow_after_secs,
+        move || slow_task.send(Msg::ConfirmationSlow),
+    )
+    .await;
+    InterruptedBurn::clear();
+    burns.finish(&record.mint);
+    if let Err(error) = confirmed {
+        task.send(Msg::Error(error));
+        return;
+    }
+    // History lives above the form, so it hears about the burn even if this
+    // form stopped waiting for it.
+    on_burn.emit(record.clone());
+    let success = BurnSuccess {
+        signature: record.signature.clone(),
+        amount: display_amount,
+        mint: record.mint.clone(),
+    };
+    task.send(Msg::Receipt(record));
+    task.send(Msg::TransactionComplete(success));
+}
+
+/// Scope handle for a spawned burn that drops its messages once the burn is
+/// aborted or the form is gone.
+#[derive(Clone)]
+struct BurnTask {
+    link: yew::html::Scope<BurnForm>,
+    aborted: Rc<Cell<bool>>,
+}
+
+impl BurnTask {
+    fn send(&self, msg: Msg) {
+        if !self.aborted.get() {
+            self.link.send_message(msg);
+        }
+    }
+}
 
 pub struct BurnForm {
+    mint: String,
+    /// The amount field exactly as typed, in the user's locale.
+    amount_text: String,
+    /// `amount_text` in canonical dot-decimal form, or empty when its
+    /// separators are ambiguous.
     amount: String,
+    amount_ambiguous: bool,
+    /// The amount came from Max, so the burn uses the raw balance as is.
+    burn_all: bool,
+    /// The last keystroke went past the token's decimals and was cut.
+    amount_clipped: bool,
+    /// How the user's locale writes numbers, for reading `amount_text`.
+    separators: NumberSeparators,
+    decimals: Option<u8>,
+    /// The decimals lookup failed, so they have to be entered by hand.
+    decimals_unavailable: bool,
+    token_program: Option<TokenProgram>,
+    /// The "mint" is a compressed NFT's asset id rather than an SPL mint.
+    compressed_asset: bool,
+    priority_fee: PriorityFee,
+    custom_fee: String,
+    compute_unit_limit: String,
+    /// Optional note sent on-chain with the burn.
+    memo: String,
     status: Option<String>,
     loading: bool,
+    phase: BurnPhase,
+    /// Abort flag of the burn currently being signed or confirmed.
+    in_flight: Option<Rc<Cell<bool>>>,
+    /// The burn is being signed on a hardware wallet, which prompts on the
+    /// device and can take a while.
+    hardware_wallet: bool,
+    pending_confirm: Option<PendingBurn>,
+    challenge_passed: bool,
+    show_base_units: bool,
+    mint_input: NodeRef,
+    amount_input: NodeRef,
+    focus_pending: bool,
+    holds_token: Option<bool>,
+    token_account: Option<TokenAccount>,
+    receipt: Option<BurnRecord>,
+    success: Option<BurnSuccess>,
+    /// Clears the visible message when it fires; dropping it cancels.
+    dismiss_timer: Option<Timeout>,
+    /// The message `dismiss_timer` was started for: (status, success signature).
+    dismiss_key: (Option<String>, Option<String>),
+    /// Token account per (cluster, wallet, mint); the same wallet exists on every cluster.
+    holdings: HashMap<(Cluster, String, String), Option<TokenAccount>>,
+    sol_balance: Option<u64>,
+    /// Lookups a manual refresh is still waiting on (token account, SOL
+    /// balance); further clicks are ignored until they're in.
+    refresh_pending: u8,
+    /// Ends a refresh whose lookups never answered.
+    refresh_timer: Option<Timeout>,
+    /// Total supply per mint on the current cluster; `None` when the lookup
+    /// failed, so it isn't retried on every keystroke.
+    supplies: HashMap<String, Option<u64>>,
+    /// Backend metadata per mint; `None` when it's unknown or the lookup
+    /// failed. Mainnet only, as that's what the backend resolves.
+    metadata: HashMap<String, Option<TokenMetadata>>,
+    wallet: WalletContext,
+    decimals_cache: DecimalsCache,
+    settings: Settings,
+    service: ServiceStatus,
+    burns_in_flight: InFlightBurns,
+    _wallet_handle: Option<ContextHandle<WalletContext>>,
+    _cache_handle: Option<ContextHandle<DecimalsCache>>,
+    _settings_handle: Option<ContextHandle<Settings>>,
+    _service_handle: Option<ContextHandle<ServiceStatus>>,
 }
 
 //yooo
 pub enum Msg {
+    UpdateMint(String),
     UpdateAmount(String),
+    UpdateMemo(String),
+    Refresh,
+    RefreshTimedOut,
+    Max,
+    Step(f64),
+    DecimalsLoaded(Cluster, String, u8),
+    DecimalsUnavailable(String),
+    EnterDecimals(String),
+    TokenProgramLoaded(Cluster, String, Option<TokenProgram>),
+    CompressedAssetDetected(Cluster, String),
+    CacheChanged(DecimalsCache),
+    WalletChanged(WalletContext),
+    SettingsChanged(Settings),
+    ServiceChanged(ServiceStatus),
+    HoldingChecked(Cluster, String, String, Option<TokenAccount>),
+    MetadataLoaded(Cluster, String, Option<TokenMetadata>),
+    SolBalanceLoaded(Cluster, String, u64),
+    SupplyLoaded(Cluster, String, Option<u64>),
+    SelectPriorityFee(String),
+    UpdateCustomFee(String),
+    UpdateComputeUnitLimit(String),
     Burn,
-    TransactionComplete(String),
+    ChallengeChanged(bool),
+    ConfirmBurn,
+    CancelBurn,
+    Submitted(BurnRecord),
+    ConfirmationSlow,
+    AbortBurn,
+    DryRunFinished(Result<String, String>),
+    DismissStatus,
+    Receipt(BurnRecord),
+    CopyReceipt,
+    CopyFailed(String),
+    TransactionComplete(BurnSuccess),
     Error(String),
+    Reset,
+    ToggleUnits,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub on_burn: Callback<BurnRecord>,
 }
 
 impl Component for BurnForm {
     type Message = Msg;
-    type Properties = ();
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (wallet, wallet_handle) = match ctx
+            .link()
+            .context::<WalletContext>(ctx.link().callback(Msg::WalletChanged))
+        {
+            Some((wallet, handle)) => (wallet, Some(handle)),
+            None => (WalletContext::default(), None),
+        };
+        let (decimals_cache, cache_handle) = match ctx
+            .link()
+            .context::<DecimalsCache>(ctx.link().callback(Msg::CacheChanged))
+        {
+            Some((cache, handle)) => (cache, Some(handle)),
+            None => (DecimalsCache::default(), None),
+        };
+        let (settings, settings_handle) = match ctx
+            .link()
+            .context::<Settings>(ctx.link().callback(Msg::SettingsChanged))
+        {
+            Some((settings, handle)) => (settings, Some(handle)),
+            None => (Settings::default(), None),
+        };
+        let (service, service_handle) = match ctx
+            .link()
+            .context::<ServiceStatus>(ctx.link().callback(Msg::ServiceChanged))
+        {
+            Some((service, handle)) => (service, Some(handle)),
+            None => (ServiceStatus::default(), None),
+        };
+        // Shared by reference and never replaced, so there's nothing to
+        // subscribe to.
+        let burns_in_flight = ctx
+            .link()
+            .context::<InFlightBurns>(Callback::noop())
+            .map(|(burns, _)| burns)
+            .unwrap_or_default();
 
-    fn create(_ctx: &Context<Self>) -> Self {
         Self {
-            amount: String::new(),
-            status: None,
-            loading: false,
+            separators: interop::number_separators(),
+            show_base_units: storage::get(BASE_UNITS_KEY).ok().flatten().unwrap_or(false),
+            _wallet_handle: wallet_handle,
+            _cache_handle: cache_handle,
+            _settings_handle: settings_handle,
+            _service_handle: service_handle,
+            ..Self::new(wallet, decimals_cache, settings, service, burns_in_flight)
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::UpdateAmount(amount) => {
-                self.amount = amount;
+            Msg::UpdateMint(mint) => {
+                self.mint = mint.trim().to_string();
+                self.burn_all = false;
+                self.compressed_asset = false;
+                self.decimals = None;
+                self.decimals_unavailable = false;
+                self.token_program = None;
+                self.resolve_decimals(ctx);
+                self.resolve_token_program(ctx);
+                self.resolve_holding(ctx);
+                self.resolve_supply(ctx);
+                self.resolve_metadata(ctx);
                 true
             }
-            Msg::Burn => {
-                if let Ok(amount) = self.amount.parse::<f64>() {
-                    if amount <= 0.0 {
-                        self.status = Some("Amount must be greater than 0".to_string());
+            Msg::UpdateAmount(text) => {
+                // The text stays as typed; only the canonical copy it's
+                // parsed from is normalized.
+                let normalized = match self.separators.normalize(&text) {
+                    Some(normalized) => normalized,
+                    None => {
+                        self.amount_text = text;
+                        self.amount = String::new();
+                        self.amount_ambiguous = true;
+                        self.amount_clipped = false;
+                        self.burn_all = false;
                         return true;
                     }
+                };
+                let (amount, clipped) = match self.decimals {
+                    Some(decimals) => limit_decimal_places(&normalized, decimals),
+                    None => (normalized.clone(), false),
+                };
+                let text = if clipped {
+                    // What was cut is the end of the fraction (and its mark),
+                    // which has no grouping marks, so cutting as many
+                    // characters off the text keeps the rest as typed.
+                    let cut = normalized.chars().count() - amount.chars().count();
+                    let trimmed = text.trim_end();
+                    let text: String = trimmed.chars().take(trimmed.chars().count() - cut).collect();
+                    // The DOM already shows the extra digit; the virtual DOM
+                    // may not differ from last render, so set it directly.
+                    if let Some(input) = self.amount_input.cast::<HtmlInputElement>() {
+                        input.set_value(&text);
+                    }
+                    text
+                } else {
+                    text
+                };
+                self.amount_clipped = clipped;
+                self.amount_text = text;
+                self.amount = amount;
+                self.amount_ambiguous = false;
+                self.burn_all = false;
+                true
+            }
+            Msg::UpdateMemo(memo) => {
+                self.memo = memo;
+                true
+            }
+            Msg::Refresh => {
+                let retry_decimals = self.decimals_unavailable;
+                if !self.begin_refresh() {
+                    return false;
+                }
+                if retry_decimals {
+                    self.resolve_decimals(ctx);
+                }
+                self.resolve_token_program(ctx);
+                self.resolve_holding(ctx);
+                self.resolve_sol_balance(ctx);
+                self.resolve_supply(ctx);
+                self.resolve_metadata(ctx);
+                let link = ctx.link().clone();
+                self.refresh_timer = Some(Timeout::new(REFRESH_TIMEOUT_MS, move || {
+                    link.send_message(Msg::RefreshTimedOut);
+                }));
+                true
+            }
+            Msg::RefreshTimedOut => {
+                self.refresh_pending = 0;
+                self.refresh_timer = None;
+                true
+            }
+            Msg::Max => {
+                self.fill_max();
+                true
+            }
+            Msg::Step(direction) => {
+                let decimals = self.decimals.unwrap_or(0);
+                let smallest = 10f64.powi(-(decimals as i32));
+                let step = (self.settings.stepper_step / smallest).round().max(1.0) * smallest;
+                let current = self.amount.parse::<f64>().unwrap_or(0.0);
+                let next = (current + direction * step).max(0.0);
+                // Stepping onto the whole balance is the same as Max: show the
+                // balance's exact digits and burn its raw amount, rather than
+                // a float that prints as 99.99999999999999.
+                if self.token_account.as_ref().map_or(false, |account| next >= account.ui_amount) {
+                    ctx.link().send_message(Msg::Max);
+                } else {
+                    let text = self.separators.localize(&format_amount(next, decimals));
+                    ctx.link().send_message(Msg::UpdateAmount(text));
+                }
+                false
+            }
+            Msg::DecimalsLoaded(cluster, mint, decimals) => {
+                if cluster != self.decimals_cache.cluster() {
+                    return false;
+                }
+                self.decimals_cache.insert(mint.clone(), decimals);
+                if mint == self.mint {
+                    self.decimals = Some(decimals);
+                }
+                true
+            }
+            Msg::DecimalsUnavailable(mint) => {
+                if mint == self.mint {
+                    self.decimals_unavailable = true;
+                    self.status = Some("Could not fetch token decimals — enter them below to continue".to_string());
+                }
+                true
+            }
+            Msg::EnterDecimals(value) => {
+                // Never cached: a hand-entered value is only trusted for this form.
+                self.decimals = match value.trim().parse::<u8>() {
+                    Ok(decimals) if decimals <= MAX_DECIMALS => Some(decimals),
+                    _ => None,
+                };
+                true
+            }
+            Msg::TokenProgramLoaded(cluster, mint, program) => {
+                if cluster != self.decimals_cache.cluster() || mint != self.mint {
+                    return false;
+                }
+                self.token_program = program;
+                if program.is_none() {
+                    self.status = Some(if self.compressed_asset {
+                        COMPRESSED_NFT_MESSAGE.to_string()
+                    } else {
+                        "This mint isn't owned by a token program".to_string()
+                    });
+                }
+                true
+            }
+            Msg::CompressedAssetDetected(cluster, mint) => {
+                if cluster != self.decimals_cache.cluster() || mint != self.mint {
+                    return false;
+                }
+                self.compressed_asset = true;
+                true
+            }
+            Msg::CacheChanged(cache) => {
+                self.decimals_cache = cache;
+                self.decimals = None;
+                self.decimals_unavailable = false;
+                self.token_program = None;
+                self.resolve_decimals(ctx);
+                self.resolve_token_program(ctx);
+                self.holdings.clear();
+                self.supplies.clear();
+                self.metadata.clear();
+                self.resolve_holding(ctx);
+                self.resolve_sol_balance(ctx);
+                self.resolve_supply(ctx);
+                self.resolve_metadata(ctx);
+                true
+            }
+            Msg::WalletChanged(wallet) => {
+                let was_connected = self.wallet.connected;
+                let previous_key = self.wallet.public_key.clone();
+                self.wallet = wallet;
+                if !self.wallet.connected {
+                    self.disconnected();
+                    return true;
+                }
+                if !was_connected {
+                    self.status = None;
+                    self.focus_pending = true;
+                } else if previous_key != self.wallet.public_key {
+                    // Another account: whatever was being signed or confirmed
+                    // belonged to the old one.
+                    self.abort_in_flight();
+                    self.loading = false;
+                    self.phase = BurnPhase::Idle;
+                    self.pending_confirm = None;
+                    self.status = Some("Wallet account changed".to_string());
+                }
+                self.resolve_holding(ctx);
+                self.resolve_sol_balance(ctx);
+                true
+            }
+            Msg::SettingsChanged(settings) => {
+                self.settings = settings;
+                true
+            }
+            Msg::ServiceChanged(service) => {
+                self.service_changed(service);
+                true
+            }
+            Msg::HoldingChecked(cluster, owner, mint, account) => {
+                // Asked on a cluster the app has since left: neither the form
+                // nor the cache should keep it.
+                if cluster != self.decimals_cache.cluster() {
+                    return false;
+                }
+                let current = self.wallet.public_key.as_deref() == Some(owner.as_str()) && mint == self.mint;
+                self.holdings.insert((cluster, owner, mint), account.clone());
+                if current {
+                    // The token account reports the mint's decimals too, which
+                    // beats asking the user when the mint lookup failed.
+                    if let (true, Some(account)) = (self.decimals_unavailable, &account) {
+                        self.decimals = Some(account.decimals);
+                        self.decimals_unavailable = false;
+                    }
+                    self.holds_token = Some(account.is_some());
+                    self.token_account = account;
+                    self.refresh_answered();
+                }
+                true
+            }
+            Msg::SupplyLoaded(cluster, mint, supply) => {
+                if cluster != self.decimals_cache.cluster() {
+                    return false;
+                }
+                self.supplies.insert(mint, supply);
+                true
+            }
+            Msg::MetadataLoaded(cluster, mint, metadata) => {
+                if cluster != self.decimals_cache.cluster() {
+                    return false;
+                }
+                // Like the token account, the metadata carries the decimals
+                // when the mint lookup failed.
+                if let (true, true, Some(metadata)) = (mint == self.mint, self.decimals_unavailable, &metadata) {
+                    self.decimals = Some(metadata.decimals);
+                    self.decimals_unavailable = false;
+                }
+                self.metadata.insert(mint, metadata);
+                true
+            }
+            Msg::SolBalanceLoaded(cluster, owner, lamports) => {
+                if cluster != self.decimals_cache.cluster() || self.wallet.public_key.as_deref() != Some(owner.as_str()) {
+                    return false;
+                }
+                self.sol_balance = Some(lamports);
+                self.refresh_answered();
+                true
+            }
+            Msg::SelectPriorityFee(preset) => {
+                self.priority_fee = match preset.as_str() {
+                    "normal" => PriorityFee::Normal,
+                    "high" => PriorityFee::High,
+                    "custom" => PriorityFee::custom(&self.custom_fee),
+                    _ => PriorityFee::None,
+                };
+                true
+            }
+            Msg::UpdateCustomFee(value) => {
+                self.priority_fee = PriorityFee::custom(&value);
+                self.custom_fee = value;
+                true
+            }
+            Msg::UpdateComputeUnitLimit(value) => {
+                self.compute_unit_limit = value;
+                true
+            }
+            Msg::Burn => self.open_confirm(),
+            Msg::ChallengeChanged(passed) => {
+                self.challenge_passed = passed;
+                true
+            }
+            Msg::CancelBurn => {
+                self.pending_confirm = None;
+                self.focus_pending = true;
+                true
+            }
+            Msg::ConfirmBurn => {
+                let pending = match self.take_confirmed() {
+                    Some(pending) => pending,
+                    None => return false,
+                };
 
-                    self.loading = true;
-                    let amount_str = self.amount.clone();
-                    let link = ctx.link().clone();
+                self.loading = true;
+                self.phase = BurnPhase::Signing;
+                self.hardware_wallet = interop::is_hardware_wallet();
+                self.receipt = None;
+                self.success = None;
+                let PendingBurn { request, amount, display_amount, .. } = pending;
+                let cluster = self.decimals_cache.cluster();
+                let polling = Polling::from(&self.settings);
+                let on_burn = ctx.props().on_burn.clone();
+                let symbol = self.symbol();
+                let aborted = Rc::new(Cell::new(false));
+                self.in_flight = Some(aborted.clone());
+                let task = BurnTask { link: ctx.link().clone(), aborted };
 
+                if self.settings.dry_run {
+                    self.phase = BurnPhase::Idle;
                     wasm_bindgen_futures::spawn_local(async move {
-                        let window = web_sys::window().unwrap();
-                        if let Ok(solana) = js_sys::Reflect::get(&window, &JsValue::from_str("solana")) {
-                            if let Ok(burn_tokens) = js_sys::Reflect::get(&solana, &JsValue::from_str("burnTokens")) {
-                                if let Some(func) = burn_tokens.dyn_ref::<js_sys::Function>() {
-                                    match func.call1(&solana, &JsValue::from_str(&amount_str)) {
-                                        Ok(_) => {
-                                            link.send_message(Msg::TransactionComplete(
-                                                format!("Successfully burned {} tokens", amount_str)
-                                            ));
-                                        }
-                                        Err(_) => {
-                                            link.send_message(Msg::Error("Failed to burn tokens".to_string()));
-                                        }
-                                    }
-                                }
-                            }
+                        let outcome = transaction::simulate_burns(cluster, std::slice::from_ref(&request))
+                            .await
+                            .map(|units| format!(
+                                "Success (dry run): burning {} would go through using {} compute units. Nothing was sent.",
+                                display_amount, units
+                            ));
+                        task.send(Msg::DryRunFinished(outcome));
+                    });
+                    return true;
+                }
+
+                // From here until it settles, another burn of this mint is
+                // refused: signing or confirming can be slow enough to retry.
+                self.burns_in_flight.start(&request.mint);
+                let burns = self.burns_in_flight.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match transaction::submit_burns(cluster, std::slice::from_ref(&request)).await {
+                        Ok(signature) => {
+                            let record = BurnRecord {
+                                timestamp: js_sys::Date::now(),
+                                mint: request.mint,
+                                symbol,
+                                amount,
+                                signature,
+                                cluster,
+                                memo: request.memo,
+                            };
+                            task.send(Msg::Submitted(record.clone()));
+                            follow_burn(task, polling, record, display_amount, on_burn, burns).await;
+                        }
+                        Err(error) => {
+                            burns.finish(&request.mint);
+                            task.send(Msg::Error(error));
+                        }
+                    }
+                });
+                true
+            }
+            Msg::Submitted(record) => {
+                self.phase = BurnPhase::Confirming(record);
+                true
+            }
+            Msg::ConfirmationSlow => {
+                if let BurnPhase::Confirming(record) = &self.phase {
+                    self.phase = BurnPhase::StillConfirming(record.clone());
+                    return true;
+                }
+                false
+            }
+            Msg::AbortBurn => {
+                if self.phase == BurnPhase::Idle {
+                    return false;
+                }
+                self.abort_in_flight();
+                self.loading = false;
+                self.phase = BurnPhase::Idle;
+                self.status = Some(
+                    "Stopped waiting for this burn. If you approved it in your wallet it may still go through — check before retrying."
+                        .to_string(),
+                );
+                true
+            }
+            Msg::DryRunFinished(outcome) => {
+                self.loading = false;
+                self.in_flight = None;
+                self.status = Some(outcome.unwrap_or_else(|error| format!("Dry run: {}", error)));
+                true
+            }
+            Msg::DismissStatus => {
+                self.dismiss_timer = None;
+                self.status = None;
+                self.success = None;
+                true
+            }
+            Msg::Receipt(record) => {
+                self.receipt = Some(record);
+                false
+            }
+            Msg::CopyReceipt => {
+                if let Some(record) = &self.receipt {
+                    let text = record.receipt(self.settings.explorer);
+                    let link = ctx.link().clone();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        if let Err(error) = interop::copy_to_clipboard(&text).await {
+                            link.send_message(Msg::CopyFailed(error));
                         }
                     });
-                } else {
-                    self.status = Some("Invalid amount".to_string());
                 }
+                false
+            }
+            Msg::CopyFailed(error) => {
+                self.status = Some(error);
                 true
             }
-            Msg::TransactionComplete(signature) => {
+            Msg::TransactionComplete(success) => {
+                telemetry::record(&self.settings, &self.service, Event::BurnSucceeded, self.decimals_cache.cluster());
                 self.loading = false;
-                self.status = Some(signature);
+                self.phase = BurnPhase::Idle;
+                self.in_flight = None;
+                self.focus_pending = true;
+                if let Some(owner) = self.wallet.public_key.clone() {
+                    self.holdings.remove(&(self.decimals_cache.cluster(), owner, self.mint.clone()));
+                }
+                self.resolve_holding(ctx);
+                self.resolve_sol_balance(ctx);
+                self.status = None;
+                self.success = Some(success);
+                self.amount_text = String::new();
                 self.amount = String::new();
+                self.amount_ambiguous = false;
+                self.burn_all = false;
                 true
             }
             Msg::Error(error) => {
+                if self.loading {
+                    telemetry::record(&self.settings, &self.service, Event::BurnFailed, self.decimals_cache.cluster());
+                }
                 self.loading = false;
+                self.phase = BurnPhase::Idle;
+                self.in_flight = None;
                 self.status = Some(error);
                 true
             }
+            Msg::ToggleUnits => {
+                self.show_base_units = !self.show_base_units;
+                if let Err(err) = storage::set(BASE_UNITS_KEY, &self.show_base_units) {
+                    log::warn!("{}", err);
+                }
+                true
+            }
+            Msg::Reset => self.clear(),
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.abort_in_flight();
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        self.schedule_dismiss(ctx);
+        if first_render && self.wallet.connected {
+            self.focus_pending = true;
+        }
+        // Leave focus alone while the confirmation dialog is up; it's picked
+        // up again once the dialog closes.
+        if self.focus_pending && self.pending_confirm.is_none() {
+            self.focus_pending = false;
+            self.focus_next_field();
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let onmintinput = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+            Msg::UpdateMint(input.value())
+        });
+
         let oninput = ctx.link().callback(|e: InputEvent| {
             let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
             Msg::UpdateAmount(input.value())
         });
 
+        let onfeechange = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target().unwrap().dyn_into().unwrap();
+            Msg::SelectPriorityFee(select.value())
+        });
+
+        let oncustomfee = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+            Msg::UpdateCustomFee(input.value())
+        });
+
+        let oncomputelimit = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+            Msg::UpdateComputeUnitLimit(input.value())
+        });
+
+        let priority_lamports = self.priority_fee.lamports(self.requested_compute_units() as u64);
+        let estimated_fee = self.estimated_fee();
+        let unwrap = transaction::is_wrapped_sol(&self.mint);
+        let disabled_reason = self.disabled_reason();
+
+        let onreset = ctx.link().callback(|_| Msg::Reset);
+        let ontoggleunits = ctx.link().callback(|_| Msg::ToggleUnits);
+
         let onsubmit = ctx.link().callback(|e: FocusEvent| {
             e.prevent_default();
             Msg::Burn
         });
 
+        if !self.wallet.connected {
+            return html! {
+                <div class="burn-form empty-state">
+                    {self.status.clone().unwrap_or_else(|| "Wallet disconnected — please reconnect".to_string())}
+                </div>
+            };
+        }
+
         html! {
             <form class="burn-form" {onsubmit}>
+                if self.settings.dry_run {
+                    <div class="dry-run-badge">{"Dry run — burns are simulated, nothing is sent"}</div>
+                }
+                <div class="input-group">
+                    <label for="mint">{"Token Mint:"}</label>
+                    <input
+                        type="text"
+                        id="mint"
+                        ref={self.mint_input.clone()}
+                        value={self.mint.clone()}
+                        oninput={onmintinput}
+                        disabled={self.loading}
+                        maxlength="128"
+                        spellcheck="false"
+                    />
+                    <button
+                        type="button"
+                        class="refresh-button"
+                        aria-label="Refresh balances"
+                        title="Refresh balances"
+                        onclick={ctx.link().callback(|_| Msg::Refresh)}
+                        disabled={self.loading || !self.wallet.connected || self.refresh_pending > 0}
+                    >
+                        <span class={if self.refresh_pending > 0 { "refresh-icon spinning" } else { "refresh-icon" }}>
+                            {"↻"}
+                        </span>
+                        {" Refresh"}
+                    </button>
+                    if self.decimals_unavailable {
+                        <label for="manual-decimals">{"Token decimals:"}</label>
+                        <input
+                            type="number"
+                            id="manual-decimals"
+                            oninput={ctx.link().callback(|e: InputEvent| {
+                                let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+                                Msg::EnterDecimals(input.value())
+                            })}
+                            disabled={self.loading}
+                            min="0"
+                            max={MAX_DECIMALS.to_string()}
+                            step="1"
+                        />
+                        <div class="input-hint">
+                            {"Check the token's decimals on an explorer. A wrong value makes the burn fail rather than burn a different amount."}
+                        </div>
+                    } else if let Some(decimals) = self.decimals {
+                        <div class="input-hint">{format!("Decimals: {}", decimals)}</div>
+                    }
+                    if let Some(metadata) = self.token_metadata() {
+                        <div class="input-hint token-name">{format!("{} ({})", metadata.name, metadata.symbol)}</div>
+                    }
+                    if self.is_nft() {
+                        <Banner severity={Severity::Warning}>
+                            {"This looks like an NFT (supply of 1, no decimals). Burning it here destroys the token \
+                              but leaves its metadata and edition accounts behind; an NFT burner also closes those \
+                              and returns their rent."}
+                        </Banner>
+                    }
+                    if self.token_account.as_ref().map_or(false, |account| account.frozen) {
+                        <Banner severity={Severity::Error}>{transaction::FROZEN_MESSAGE}</Banner>
+                    }
+                    if self.token_program == Some(TokenProgram::Token2022) {
+                        <div class="input-hint info">
+                            {"This is a Token-2022 mint. Extensions such as transfer fees or hooks may apply."}
+                        </div>
+                    }
+                    if self.holds_token == Some(false) {
+                        <div class="input-hint error">{"You don't hold this token"}</div>
+                    }
+                </div>
                 <div class="input-group">
                     <label for="amount">{"Amount to Burn:"}</label>
+                    <div class="amount-row">
+                        <button
+                            type="button"
+                            class="amount-stepper"
+                            aria-label="Decrease amount"
+                            onclick={ctx.link().callback(|_| Msg::Step(-1.0))}
+                            disabled={self.loading}
+                        >
+                            {"−"}
+                        </button>
+                        <input
+                            type="text"
+                            inputmode="decimal"
+                            id="amount"
+                            ref={self.amount_input.clone()}
+                            value={self.amount_text.clone()}
+                            {oninput}
+                            disabled={self.loading || unwrap}
+                            autocomplete="off"
+                            spellcheck="false"
+                        />
+                        <button
+                            type="button"
+                            class="amount-stepper"
+                            aria-label="Increase amount"
+                            onclick={ctx.link().callback(|_| Msg::Step(1.0))}
+                            disabled={self.loading}
+                        >
+                            {"+"}
+                        </button>
+                    </div>
+                    if let Some(account) = &self.token_account {
+                        <div class="input-hint">
+                            if self.show_base_units {
+                                {format!("Balance: {} base units", account.amount)}
+                            } else {
+                                {format!("Balance: {}", account.ui_amount)}
+                            }
+                            if !unwrap {
+                                <button
+                                    type="button"
+                                    class="unit-toggle"
+                                    onclick={ctx.link().callback(|_| Msg::Max)}
+                                    disabled={self.loading}
+                                >
+                                    {"Max"}
+                                </button>
+                            }
+                            <button type="button" class="unit-toggle" onclick={ontoggleunits.clone()}>
+                                {if self.show_base_units { "Show tokens" } else { "Show base units" }}
+                            </button>
+                        </div>
+                        if let (true, Some(decimals)) = (self.amount_clipped, self.decimals) {
+                            <div class="input-hint error">
+                                {match decimals {
+                                    0 => "This token has no decimal places".to_string(),
+                                    1 => "Max 1 decimal for this token".to_string(),
+                                    decimals => format!("Max {} decimals for this token", decimals),
+                                }}
+                            </div>
+                        }
+                        if self.show_base_units {
+                            if let Some(units) = self.decimals.and_then(|decimals| {
+                                amount_to_base_units(&self.amount, decimals, self.settings.rounding)
+                            }) {
+                                <div class="input-hint">{format!("= {} base units", units)}</div>
+                            }
+                        }
+                        if unwrap {
+                            <div class="input-hint">
+                                {format!(
+                                    "Unwrapping closes the account and recovers {} SOL",
+                                    lamports_to_sol(account.amount + transaction::TOKEN_ACCOUNT_RENT_LAMPORTS)
+                                )}
+                            </div>
+                        } else {
+                            {self.balance_share()}
+                            {self.supply_share()}
+                        }
+                    }
+                </div>
+                <div class="input-group">
+                    <label for="memo">{"Memo (optional):"}</label>
+                    <input
+                        type="text"
+                        id="memo"
+                        value={self.memo.clone()}
+                        oninput={ctx.link().callback(|e: InputEvent| {
+                            let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+                            Msg::UpdateMemo(input.value())
+                        })}
+                        disabled={self.loading}
+                        maxlength={MAX_MEMO_CHARS.to_string()}
+                        placeholder="Why are you burning these?"
+                    />
+                    <div class="input-hint">{"Recorded publicly on-chain with the burn."}</div>
+                </div>
+                <div class="input-group">
+                    <label for="priority-fee">{"Priority Fee:"}</label>
+                    <select id="priority-fee" onchange={onfeechange} disabled={self.loading}>
+                        <option value="none" selected={self.priority_fee == PriorityFee::None}>{"None"}</option>
+                        <option value="normal" selected={self.priority_fee == PriorityFee::Normal}>{"Normal"}</option>
+                        <option value="high" selected={self.priority_fee == PriorityFee::High}>{"High"}</option>
+                        <option value="custom" selected={matches!(self.priority_fee, PriorityFee::Custom(_))}>{"Custom"}</option>
+                    </select>
+                    if matches!(self.priority_fee, PriorityFee::Custom(_)) {
+                        <input
+                            type="number"
+                            placeholder="micro-lamports per CU"
+                            value={self.custom_fee.clone()}
+                            oninput={oncustomfee}
+                            disabled={self.loading}
+                            step="1"
+                            min="0"
+                            max={MAX_CUSTOM_PRIORITY_FEE.to_string()}
+                        />
+                    }
+                    <label for="compute-unit-limit">{"Compute Unit Limit:"}</label>
                     <input
                         type="number"
-                        id="amount"
-                        value={self.amount.clone()}
-                        {oninput}
+                        id="compute-unit-limit"
+                        value={self.compute_unit_limit.clone()}
+                        oninput={oncomputelimit}
                         disabled={self.loading}
-                        step="0.000001"
-                        min="0"
+                        step="1000"
+                        min="1"
                     />
+                    <div class="input-hint">
+                        if self.show_base_units {
+                            {format!(
+                                "Estimated fee: {} lamports (priority: {} lamports)",
+                                estimated_fee,
+                                priority_lamports
+                            )}
+                        } else {
+                            {format!(
+                                "Estimated fee: {} SOL (priority: {} SOL)",
+                                lamports_to_sol(estimated_fee),
+                                lamports_to_sol(priority_lamports)
+                            )}
+                        }
+                    </div>
+                    if self.lacks_fee_sol() {
+                        <div class="error">{"Insufficient SOL for transaction fee"}</div>
+                    }
                 </div>
-                <button type="submit" disabled={self.loading}>
+                <button
+                    type="submit"
+                    class={if unwrap || self.settings.dry_run {
+                        classes!()
+                    } else {
+                        classes!("danger-button", "burn-button", self.burn_emphasis())
+                    }}
+                    disabled={disabled_reason.is_some()}
+                    aria-describedby={disabled_reason.map(|_| "burn-disabled-reason")}
+                >
                     if self.loading {
                         {"Processing..."}
+                    } else if self.settings.dry_run {
+                        {"Simulate Burn"}
+                    } else if unwrap {
+                        {"Unwrap SOL"}
                     } else {
-                        {"Burn Tokens"}
+                        {"🔥 Permanently Burn"}
                     }
                 </button>
+                <Tooltip label="What is burning?" id="burn-explainer">
+                    {"Burning permanently destroys the tokens: they leave your wallet and the total supply for good. It cannot be undone. "}
+                    <a href="https://spl.solana.com/token#burning" target="_blank" rel="noopener noreferrer">
+                        {"Learn more"}
+                    </a>
+                </Tooltip>
+                <button type="button" class="secondary-button" onclick={onreset} disabled={self.loading}>
+                    {"Clear"}
+                </button>
+                if let Some(reason) = disabled_reason {
+                    <div class="input-hint" id="burn-disabled-reason">{reason}</div>
+                }
+                if let Some(pending) = &self.pending_confirm {
+                    {self.confirm_dialog(ctx, pending)}
+                }
+                {self.phase_notice(ctx)}
+                if let Some(success) = &self.success {
+                    {self.success_notice(ctx, success)}
+                }
                 if let Some(status) = &self.status {
                     <div class={if status.contains("Success") { "status-message success" } else { "status-message error" }}>
                         {status}
@@ -126,3 +1160,1091 @@ impl Component for BurnForm {
         }
     }
 }
+
+impl BurnForm {
+    /// A blank form over the given contexts, not subscribed to any of them.
+    fn new(
+        wallet: WalletContext,
+        decimals_cache: DecimalsCache,
+        settings: Settings,
+        service: ServiceStatus,
+        burns_in_flight: InFlightBurns,
+    ) -> Self {
+        Self {
+            mint: String::new(),
+            amount_text: String::new(),
+            amount: String::new(),
+            amount_ambiguous: false,
+            burn_all: false,
+            amount_clipped: false,
+            separators: NumberSeparators::default(),
+            decimals: None,
+            decimals_unavailable: false,
+            token_program: None,
+            compressed_asset: false,
+            priority_fee: PriorityFee::default(),
+            custom_fee: String::new(),
+            compute_unit_limit: DEFAULT_COMPUTE_UNIT_LIMIT.to_string(),
+            memo: String::new(),
+            status: None,
+            loading: false,
+            phase: BurnPhase::Idle,
+            in_flight: None,
+            hardware_wallet: false,
+            pending_confirm: None,
+            challenge_passed: false,
+            show_base_units: false,
+            mint_input: NodeRef::default(),
+            amount_input: NodeRef::default(),
+            focus_pending: false,
+            holds_token: None,
+            token_account: None,
+            receipt: None,
+            success: None,
+            dismiss_timer: None,
+            dismiss_key: (None, None),
+            holdings: HashMap::new(),
+            sol_balance: None,
+            refresh_pending: 0,
+            refresh_timer: None,
+            supplies: HashMap::new(),
+            metadata: HashMap::new(),
+            wallet,
+            decimals_cache,
+            settings,
+            service,
+            burns_in_flight,
+            _wallet_handle: None,
+            _cache_handle: None,
+            _settings_handle: None,
+            _service_handle: None,
+        }
+    }
+
+    /// Fills in the whole balance, marking the burn to take the account's
+    /// raw amount rather than whatever the text parses back to.
+    fn fill_max(&mut self) {
+        if let Some(account) = &self.token_account {
+            self.amount = base_units_to_decimal_str(account.amount, account.decimals);
+            self.amount_text = self.separators.localize(&self.amount);
+            self.amount_ambiguous = false;
+            self.burn_all = true;
+        }
+    }
+
+    /// Opens the confirmation dialog for the burn the form describes, or
+    /// says why it can't.
+    fn open_confirm(&mut self) -> bool {
+        // Enter and a click can both land while the dialog is up (or
+        // a burn is running); only the first opens anything, so the
+        // open dialog and its challenge are left as they are.
+        if self.pending_confirm.is_some() || self.loading {
+            return false;
+        }
+        if let Err(error) = validation::validate(&self.burn_check()) {
+            self.status = Some(error.message().to_string());
+            return true;
+        }
+
+        // Unwrapping closes the account, so it always takes the whole balance.
+        let unwrap = transaction::is_wrapped_sol(&self.mint);
+        let amount_text = match (&self.token_account, unwrap) {
+            (Some(account), true) => base_units_to_decimal_str(account.amount, account.decimals),
+            _ => self.amount.clone(),
+        };
+        if let Ok(amount) = amount_text.parse::<f64>() {
+            let (owner, decimals, token_program) =
+                match (&self.wallet.public_key, self.decimals, self.token_program) {
+                    (Some(owner), Some(decimals), Some(program)) => (owner.clone(), decimals, program),
+                    (None, _, _) => {
+                        self.status = Some("Wallet address unavailable".to_string());
+                        return true;
+                    }
+                    // `validate` has already required the token details.
+                    _ => return false,
+                };
+
+            let base_units = match (&self.token_account, unwrap || self.burn_all) {
+                // Taken straight from the account so "burn all" leaves
+                // nothing behind, whatever the float round trip does.
+                (Some(account), true) => account.amount,
+                _ => match amount_to_base_units(&amount_text, decimals, self.settings.rounding) {
+                    Some(units) => units,
+                    None => {
+                        self.status = Some("Amount is too large".to_string());
+                        return true;
+                    }
+                },
+            };
+            if amount_diverges(&amount_text, base_units, decimals) {
+                self.status = Some(format!(
+                    "The amount to burn ({} base units) doesn't match the {} you entered, so nothing was sent. Please report this.",
+                    base_units, amount_text
+                ));
+                return true;
+            }
+            let request = BurnRequest {
+                owner,
+                mint: self.mint.clone(),
+                amount: base_units,
+                decimals,
+                token_program,
+                token_account: self.token_account.as_ref().map(|account| account.address.clone()),
+                priority_fee_micro_lamports: self.priority_fee.micro_lamports(),
+                compute_unit_limit: self.compute_unit_limit(),
+                memo: sanitize_memo(&self.memo),
+            };
+            let near_full_supply = self.near_full_supply(base_units);
+            let share = self
+                .token_account
+                .as_ref()
+                .filter(|account| account.ui_amount > 0.0)
+                .map_or(0.0, |account| amount / account.ui_amount * 100.0);
+
+            self.status = None;
+            self.challenge_passed = false;
+            self.pending_confirm = Some(PendingBurn {
+                request,
+                amount,
+                display_amount: amount_text,
+                requires_challenge: !unwrap
+                    && (self.burn_all || near_full_supply || share > self.settings.confirm_challenge_percent),
+                entire_balance: self.burn_all,
+                near_full_supply,
+            });
+        } else {
+            self.status = Some("Invalid amount".to_string());
+        }
+        true
+    }
+
+    /// The pending burn, once the dialog's challenge (if any) is passed and
+    /// burning isn't paused for maintenance.
+    fn take_confirmed(&mut self) -> Option<PendingBurn> {
+        if self.service.maintenance {
+            return None;
+        }
+        let ready = self
+            .pending_confirm
+            .as_ref()
+            .map_or(false, |pending| !pending.requires_challenge || self.challenge_passed);
+        if !ready {
+            return None;
+        }
+        self.pending_confirm.take()
+    }
+
+    /// Maintenance closes an open confirmation dialog.
+    fn service_changed(&mut self, service: ServiceStatus) {
+        if service.maintenance {
+            self.pending_confirm = None;
+        }
+        self.service = service;
+    }
+
+    fn compute_unit_limit(&self) -> u32 {
+        match self.compute_unit_limit.parse() {
+            Ok(units) if units > 0 => units,
+            _ => DEFAULT_COMPUTE_UNIT_LIMIT,
+        }
+    }
+
+    /// A mint with no decimals and a supply of one is treated as an NFT.
+    fn is_nft(&self) -> bool {
+        self.decimals == Some(0) && self.supplies.get(&self.mint) == Some(&Some(1))
+    }
+
+    /// The compute unit limit the transaction will ask for: the chosen one,
+    /// plus what the memo is budgeted when there is one.
+    fn requested_compute_units(&self) -> u32 {
+        let memo_units = if sanitize_memo(&self.memo).is_some() { transaction::MEMO_COMPUTE_UNITS } else { 0 };
+        self.compute_unit_limit().saturating_add(memo_units)
+    }
+
+    /// Base fee plus the priority fee for the requested compute units.
+    fn estimated_fee(&self) -> u64 {
+        BASE_FEE_LAMPORTS.saturating_add(self.priority_fee.lamports(self.requested_compute_units() as u64))
+    }
+
+    /// Whether the wallet's SOL balance is known to be too low to pay for the
+    /// burn. An unknown balance doesn't block; the wallet will catch it.
+    fn lacks_fee_sol(&self) -> bool {
+        self.sol_balance.map_or(false, |lamports| lamports < self.estimated_fee())
+    }
+
+    /// How loudly the burn button warns, by the share of the balance the
+    /// entered amount would destroy.
+    fn burn_emphasis(&self) -> &'static str {
+        let share = match (&self.token_account, self.amount.parse::<f64>()) {
+            _ if self.burn_all => 1.0,
+            (Some(account), Ok(amount)) if account.ui_amount > 0.0 => amount / account.ui_amount,
+            _ => 0.0,
+        };
+        if share >= 0.75 {
+            "emphasis-high"
+        } else if share >= 0.25 {
+            "emphasis-medium"
+        } else {
+            "emphasis-low"
+        }
+    }
+
+    /// Why the burn button can't be pressed right now, or `None` when it can.
+    /// This is the only place that decides whether the button is enabled;
+    /// the checks on submit still run, for what only shows up then.
+    fn disabled_reason(&self) -> Option<&'static str> {
+        validation::validate(&self.burn_check()).err().map(|error| error.message())
+    }
+
+    /// The form's state as the validation pipeline sees it.
+    fn burn_check(&self) -> BurnCheck<'_> {
+        BurnCheck {
+            connected: self.wallet.connected,
+            busy: self.loading,
+            maintenance: self.service.maintenance,
+            mint: self.mint.trim(),
+            compressed_asset: self.compressed_asset,
+            token_details_loaded: self.decimals.is_some() && self.token_program.is_some(),
+            holds_token: self.holds_token,
+            balance: self.token_account.as_ref().map(|account| account.amount),
+            frozen: self.token_account.as_ref().map_or(false, |account| account.frozen),
+            lacks_fee_sol: self.lacks_fee_sol(),
+            already_in_flight: self.burns_in_flight.contains(self.mint.trim()),
+            amount_ambiguous: self.amount_ambiguous,
+            amount: &self.amount,
+            decimals: self.decimals,
+            rounding: self.settings.rounding,
+        }
+    }
+
+    /// Moves focus to the first field the user still has to fill in.
+    fn focus_next_field(&self) {
+        let target = if self.mint.is_empty() { &self.mint_input } else { &self.amount_input };
+        if let Some(input) = target.cast::<HtmlInputElement>() {
+            let _ = input.focus();
+        }
+    }
+
+    /// Progress of a burn that has left the confirmation dialog.
+    fn phase_notice(&self, ctx: &Context<Self>) -> Html {
+        let onabort = ctx.link().callback(|_| Msg::AbortBurn);
+        let abort = html! {
+            <button type="button" class="secondary-button" onclick={onabort}>{"Stop waiting"}</button>
+        };
+        match &self.phase {
+            BurnPhase::Idle => html! {},
+            BurnPhase::Signing if self.hardware_wallet => html! {
+                <div class="status-message pending">
+                    {"Confirm the burn on your Ledger device. Signing on a hardware wallet can take a minute; \
+                      if the device asks for blind signing, enable it in the Solana app's settings and try again."}
+                    {abort}
+                </div>
+            },
+            BurnPhase::Signing => html! {
+                <div class="status-message">{"Waiting for the wallet to approve..."}{abort}</div>
+            },
+            BurnPhase::Confirming(_) => html! {
+                <div class="status-message">{"Confirming burn..."}{abort}</div>
+            },
+            BurnPhase::StillConfirming(record) => html! {
+                <div class="status-message pending">
+                    {"Still confirming — this is taking longer than usual. No need to resubmit."}
+                    <a href={record.explorer_url(self.settings.explorer)} target="_blank" rel="noopener noreferrer">
+                        {"View on explorer"}
+                    </a>
+                    {abort}
+                </div>
+            },
+        }
+    }
+
+    /// Restarts the auto-dismiss timer whenever the visible message changes,
+    /// so a timer never clears a newer message than the one it was set for.
+    fn schedule_dismiss(&mut self, ctx: &Context<Self>) {
+        let key = (
+            self.status.clone(),
+            self.success.as_ref().map(|success| success.signature.clone()),
+        );
+        if key == self.dismiss_key {
+            return;
+        }
+        self.dismiss_key = key;
+        self.dismiss_timer = None;
+
+        let is_success = self.success.is_some()
+            || self.status.as_deref().map_or(false, |status| status.contains("Success"));
+        let seconds = match (&self.status, &self.success) {
+            (None, None) => return,
+            _ if is_success => self.settings.success_dismiss_seconds,
+            _ => self.settings.error_dismiss_seconds,
+        };
+        if seconds == 0 {
+            return;
+        }
+        let link = ctx.link().clone();
+        self.dismiss_timer = Some(Timeout::new(seconds * 1_000, move || {
+            link.send_message(Msg::DismissStatus);
+        }));
+    }
+
+    fn abort_in_flight(&mut self) {
+        if let Some(aborted) = self.in_flight.take() {
+            aborted.set(true);
+        }
+    }
+
+    fn success_notice(&self, ctx: &Context<Self>, success: &BurnSuccess) -> Html {
+        let text = if transaction::is_wrapped_sol(&success.mint) {
+            format!("Successfully unwrapped {} SOL", success.amount)
+        } else {
+            format!("Successfully burned {} tokens of {}", success.amount, abbreviate(&success.mint))
+        };
+
+        html! {
+            <div class="status-message success">
+                <div class="token-identity">
+                    {self.token_logo(&success.mint)}
+                    {text}
+                </div>
+                <div>
+                    {"Transaction: "}
+                    <AddressDisplay
+                        address={success.signature.clone()}
+                        kind={AddressKind::Signature}
+                        cluster={self.decimals_cache.cluster()}
+                    />
+                </div>
+                if let Some(record) = &self.receipt {
+                    {self.receipt_actions(ctx, record)}
+                }
+            </div>
+        }
+    }
+
+    fn receipt_actions(&self, ctx: &Context<Self>, record: &BurnRecord) -> Html {
+        let text = record.receipt(self.settings.explorer);
+        let tweet = format!(
+            "https://twitter.com/intent/tweet?text={}",
+            String::from(js_sys::encode_uri_component(&text))
+        );
+        let oncopy = ctx.link().callback(|_| Msg::CopyReceipt);
+
+        html! {
+            <div class="receipt-actions">
+                <button type="button" onclick={oncopy}>{"Copy receipt"}</button>
+                <a href={tweet} target="_blank" rel="noopener noreferrer">{"Share on X"}</a>
+            </div>
+        }
+    }
+
+    /// Bar showing how much of the balance the entered amount would destroy.
+    fn balance_share(&self) -> Html {
+        let balance = self.token_account.as_ref().map(|account| account.ui_amount).unwrap_or(0.0);
+        let amount = match self.amount.parse::<f64>() {
+            Ok(amount) if amount > 0.0 && balance > 0.0 => amount,
+            _ => return html! {},
+        };
+        let percent = (amount / balance * 100.0).min(100.0);
+        let class = if percent > 90.0 { "balance-share danger" } else { "balance-share" };
+
+        html! {
+            <div class={class}>
+                <div class="balance-share-track">
+                    <div class="balance-share-fill" style={format!("width: {:.1}%", percent)}></div>
+                </div>
+                <div class="input-hint">{format!("You're burning {:.0}% of your balance", percent)}</div>
+            </div>
+        }
+    }
+
+    /// Total supply of the current mint, once loaded.
+    fn supply(&self) -> Option<u64> {
+        match self.supplies.get(&self.mint) {
+            Some(Some(supply)) if *supply > 0 => Some(*supply),
+            _ => None,
+        }
+    }
+
+    /// Whether burning `base_units` reaches the configured share of supply.
+    fn near_full_supply(&self, base_units: u64) -> bool {
+        self.supply().map_or(false, |supply| {
+            is_near_full_supply(base_units, supply, self.settings.full_supply_warning_percent)
+        })
+    }
+
+    /// How much of the mint's total supply the entered amount is, with a
+    /// prominent warning when it's most or all of it.
+    fn supply_share(&self) -> Html {
+        let supply = match self.supply() {
+            Some(supply) => supply,
+            None => return html! {},
+        };
+        let base_units = match self
+            .decimals
+            .and_then(|decimals| amount_to_base_units(&self.amount, decimals, self.settings.rounding))
+        {
+            Some(units) if units > 0 => units,
+            _ => return html! {},
+        };
+        let percent = base_units as f64 / supply as f64 * 100.0;
+        if self.near_full_supply(base_units) {
+            return html! {
+                <Banner severity={Severity::Error}>
+                    <strong>{format!("This burns {}% of the token's entire supply.", format_amount(percent, 2))}</strong>
+                    {" If you run this project, make sure you mean to destroy it: there is no way to mint it back \
+                      unless you still hold the mint authority."}
+                </Banner>
+            };
+        }
+        let text = if percent < 0.0001 {
+            "This burns less than 0.0001% of total supply".to_string()
+        } else {
+            format!("This burns {}% of total supply", format_amount(percent, 4))
+        };
+
+        html! { <div class="input-hint">{text}</div> }
+    }
+
+    fn confirm_dialog(&self, ctx: &Context<Self>, pending: &PendingBurn) -> Html {
+        let oncancel = ctx.link().callback(|_| Msg::CancelBurn);
+        let onconfirm = ctx.link().callback(|_| Msg::ConfirmBurn);
+        let onchallenge = ctx.link().callback(Msg::ChallengeChanged);
+        let can_confirm = !pending.requires_challenge || self.challenge_passed;
+
+        if transaction::is_wrapped_sol(&pending.request.mint) {
+            return html! {
+                <Modal title="Confirm unwrap" on_cancel={oncancel.clone()}>
+                    <p>
+                        {format!(
+                            "You are about to unwrap {} wSOL and close its token account, recovering {} SOL.",
+                            pending.display_amount,
+                            lamports_to_sol(pending.request.amount + transaction::TOKEN_ACCOUNT_RENT_LAMPORTS)
+                        )}
+                    </p>
+                    <div class="modal-actions">
+                        <button type="button" class="secondary-button" onclick={oncancel.reform(|_: MouseEvent| ())}>
+                            {"Cancel"}
+                        </button>
+                        <button type="button" onclick={onconfirm}>
+                            {"Confirm Unwrap"}
+                        </button>
+                    </div>
+                </Modal>
+            };
+        }
+
+        html! {
+            <Modal title="Confirm burn" on_cancel={oncancel.clone()}>
+                <p>
+                    {format!("You are about to permanently burn {} of", pending.display_amount)}
+                </p>
+                <div class="token-identity">
+                    {self.token_logo(&pending.request.mint)}
+                    if let Some(metadata) = self.metadata.get(&pending.request.mint).and_then(Option::as_ref) {
+                        <strong>{format!("{} ({})", metadata.name, metadata.symbol)}</strong>
+                    }
+                    <AddressDisplay
+                        address={pending.request.mint.clone()}
+                        kind={AddressKind::Mint}
+                        cluster={self.decimals_cache.cluster()}
+                    />
+                </div>
+                if pending.entire_balance {
+                    <p class="error"><strong>{"This burns your entire balance of this token."}</strong></p>
+                }
+                if let Some(memo) = &pending.request.memo {
+                    <p>{"Memo: "}<em>{memo.clone()}</em></p>
+                }
+                if pending.near_full_supply {
+                    <p class="error"><strong>{"This burns most or all of the token's total supply."}</strong></p>
+                }
+                if self.settings.dry_run {
+                    <p class="dry-run-badge">{"Dry run: this burn will only be simulated."}</p>
+                } else {
+                    <p class="error">{"This cannot be undone."}</p>
+                }
+                if pending.requires_challenge {
+                    <ConfirmChallenge phrase={CHALLENGE_PHRASE} on_change={onchallenge} />
+                }
+                <div class="modal-actions">
+                    <button type="button" class="secondary-button" onclick={oncancel.reform(|_: MouseEvent| ())}>
+                        {"Cancel"}
+                    </button>
+                    <button type="button" class="danger-button" onclick={onconfirm} disabled={!can_confirm}>
+                        {"Confirm Burn"}
+                    </button>
+                </div>
+            </Modal>
+        }
+    }
+
+    /// The Clear button: starts over, except while a burn is running.
+    fn clear(&mut self) -> bool {
+        if self.loading {
+            return false;
+        }
+        self.reset();
+        self.focus_pending = true;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.mint = String::new();
+        self.amount_text = String::new();
+        self.amount = String::new();
+        self.amount_ambiguous = false;
+        self.burn_all = false;
+        self.amount_clipped = false;
+        self.memo = String::new();
+        self.decimals = None;
+        self.decimals_unavailable = false;
+        self.compressed_asset = false;
+        self.holds_token = None;
+        self.token_account = None;
+        self.receipt = None;
+        self.success = None;
+        self.pending_confirm = None;
+        self.status = None;
+        self.loading = false;
+        self.phase = BurnPhase::Idle;
+    }
+
+    /// Forgets what a manual refresh looks up again: balances move with
+    /// outside transactions, and supply, program and metadata can change too.
+    /// Decimals never do, so they're only retried when the first lookup
+    /// failed. Returns `false` when there's nothing to refresh or a refresh
+    /// (or burn) is already running.
+    fn begin_refresh(&mut self) -> bool {
+        if self.refresh_pending > 0 || self.loading {
+            return false;
+        }
+        let owner = match self.wallet.public_key.clone() {
+            Some(owner) => owner,
+            None => return false,
+        };
+        self.holdings.remove(&(self.decimals_cache.cluster(), owner, self.mint.clone()));
+        self.supplies.remove(&self.mint);
+        self.metadata.remove(&self.mint);
+        self.decimals_unavailable = false;
+        self.refresh_pending = if is_valid_mint(&self.mint) { 2 } else { 1 };
+        true
+    }
+
+    /// Clears the form once the wallet is gone. A burn still running reports
+    /// to a form that has moved on, so it's cut loose first.
+    fn disconnected(&mut self) {
+        self.abort_in_flight();
+        self.reset();
+        self.sol_balance = None;
+        self.status = Some("Wallet disconnected — please reconnect".to_string());
+    }
+
+    /// Counts down a manual refresh as its lookups answer.
+    fn refresh_answered(&mut self) {
+        if self.refresh_pending == 0 {
+            return;
+        }
+        self.refresh_pending -= 1;
+        if self.refresh_pending == 0 {
+            self.refresh_timer = None;
+        }
+    }
+
+    /// Fills `decimals` for the current mint from the cache, fetching it only
+    /// when this mint hasn't been seen on the current cluster yet.
+    fn resolve_decimals(&mut self, ctx: &Context<Self>) {
+        if !is_valid_mint(&self.mint) {
+            return;
+        }
+        if let Some(decimals) = self.decimals_cache.get(&self.mint) {
+            self.decimals = Some(decimals);
+            return;
+        }
+
+        let mint = self.mint.clone();
+        let cluster = self.decimals_cache.cluster();
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            match rpc::get_token_decimals(cluster, &mint).await {
+                Ok(decimals) => link.send_message(Msg::DecimalsLoaded(cluster, mint, decimals)),
+                Err(_) => link.send_message(Msg::DecimalsUnavailable(mint)),
+            }
+        });
+    }
+
+    fn resolve_token_program(&self, ctx: &Context<Self>) {
+        if !is_valid_mint(&self.mint) {
+            return;
+        }
+
+        let mint = self.mint.clone();
+        let cluster = self.decimals_cache.cluster();
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Ok(owner) = rpc::get_account_owner(cluster, &mint).await {
+                let program = owner.as_deref().and_then(TokenProgram::from_owner);
+                // Compressed NFTs have no on-chain mint account, so only ask
+                // DAS about ids that aren't token mints.
+                if owner.is_none() && rpc::is_compressed_asset(cluster, &mint).await.unwrap_or(false) {
+                    link.send_message(Msg::CompressedAssetDetected(cluster, mint.clone()));
+                }
+                link.send_message(Msg::TokenProgramLoaded(cluster, mint, program));
+            }
+        });
+    }
+
+    /// Checks that the connected wallet has a token account for the current
+    /// mint, reusing earlier answers for the same wallet and mint on this
+    /// cluster.
+    fn resolve_holding(&mut self, ctx: &Context<Self>) {
+        self.holds_token = None;
+        self.token_account = None;
+        let owner = match &self.wallet.public_key {
+            Some(owner) if is_valid_address(owner) => owner.clone(),
+            _ => return,
+        };
+        if !is_valid_mint(&self.mint) {
+            return;
+        }
+        let key = (self.decimals_cache.cluster(), owner, self.mint.clone());
+        if let Some(account) = self.holdings.get(&key) {
+            self.holds_token = Some(account.is_some());
+            self.token_account = account.clone();
+            return;
+        }
+
+        let (cluster, owner, mint) = key;
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Ok(account) = rpc::find_token_account(cluster, &owner, &mint).await {
+                link.send_message(Msg::HoldingChecked(cluster, owner, mint, account));
+            }
+        });
+    }
+
+    /// Fetches the mint's total supply once per cluster.
+    fn resolve_supply(&self, ctx: &Context<Self>) {
+        if !is_valid_mint(&self.mint) || self.supplies.contains_key(&self.mint) {
+            return;
+        }
+
+        let mint = self.mint.clone();
+        let cluster = self.decimals_cache.cluster();
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let supply = rpc::get_token_supply(cluster, &mint).await.ok().map(|supply| supply.amount);
+            link.send_message(Msg::SupplyLoaded(cluster, mint, supply));
+        });
+    }
+
+    /// Backend metadata for the current mint, once it's in.
+    fn token_metadata(&self) -> Option<&TokenMetadata> {
+        self.metadata.get(&self.mint).and_then(Option::as_ref)
+    }
+
+    /// The mint's logo when its metadata has one; nothing otherwise, rather
+    /// than a placeholder for every token the backend doesn't know.
+    fn token_logo(&self, mint: &str) -> Html {
+        match self.metadata.get(mint).and_then(Option::as_ref) {
+            Some(metadata) if metadata.logo.is_some() => html! {
+                <TokenLogo
+                    src={metadata.logo.clone().map(AttrValue::from)}
+                    symbol={metadata.symbol.clone()}
+                />
+            },
+            _ => html! {},
+        }
+    }
+
+    fn symbol(&self) -> Option<String> {
+        self.token_metadata()
+            .map(|metadata| metadata.symbol.clone())
+            .filter(|symbol| !symbol.is_empty())
+    }
+
+    /// Asks the backend for the mint's name and symbol once per mint, when
+    /// it's up and the app is on mainnet.
+    fn resolve_metadata(&self, ctx: &Context<Self>) {
+        if !is_valid_mint(&self.mint)
+            || self.metadata.contains_key(&self.mint)
+            || !self.service.backend_available
+            || self.decimals_cache.cluster() != Cluster::Mainnet
+        {
+            return;
+        }
+
+        let mint = self.mint.clone();
+        let cluster = self.decimals_cache.cluster();
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let metadata = api::fetch_token_metadata(&mint).await.ok().flatten();
+            link.send_message(Msg::MetadataLoaded(cluster, mint, metadata));
+        });
+    }
+
+    /// Fetches the connected wallet's SOL balance for the fee check.
+    fn resolve_sol_balance(&mut self, ctx: &Context<Self>) {
+        self.sol_balance = None;
+        let owner = match &self.wallet.public_key {
+            Some(owner) if is_valid_address(owner) => owner.clone(),
+            _ => return,
+        };
+        let cluster = self.decimals_cache.cluster();
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Ok(lamports) = rpc::get_balance(cluster, &owner).await {
+                link.send_message(Msg::SolBalanceLoaded(cluster, owner, lamports));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::AmountError;
+
+    const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const SIGNATURE: &str =
+        "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+
+    fn record(cluster: Cluster) -> BurnRecord {
+        BurnRecord {
+            timestamp: 1_700_000_000_000.0,
+            mint: MINT.to_string(),
+            symbol: Some("USDC".to_string()),
+            amount: 1.5,
+            signature: SIGNATURE.to_string(),
+            cluster,
+            memo: Some("gm".to_string()),
+        }
+    }
+
+    const OWNER: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    fn connected() -> WalletContext {
+        WalletContext { connected: true, public_key: Some(OWNER.to_string()) }
+    }
+
+    /// A connected form over fresh contexts, with nothing entered.
+    fn form() -> BurnForm {
+        BurnForm::new(
+            connected(),
+            DecimalsCache::default(),
+            Settings::default(),
+            ServiceStatus::default(),
+            InFlightBurns::default(),
+        )
+    }
+
+    fn account(amount: u64, decimals: u8) -> TokenAccount {
+        TokenAccount {
+            address: "Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr".to_string(),
+            mint: MINT.to_string(),
+            program: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".to_string(),
+            amount,
+            decimals,
+            ui_amount: amount as f64 / 10f64.powi(decimals as i32),
+            frozen: false,
+        }
+    }
+
+    #[test]
+    fn refresh_forgets_the_mints_lookups() {
+        let mut form = form();
+        form.mint = MINT.to_string();
+        let key = (Cluster::Mainnet, OWNER.to_string(), MINT.to_string());
+        form.holdings.insert(key.clone(), Some(account(1, 0)));
+        form.supplies.insert(MINT.to_string(), Some(1));
+        form.metadata.insert(MINT.to_string(), None);
+        form.metadata.insert("other".to_string(), None);
+
+        assert!(form.begin_refresh());
+        assert!(!form.holdings.contains_key(&key));
+        assert!(!form.supplies.contains_key(MINT));
+        assert!(!form.metadata.contains_key(MINT));
+        assert!(form.metadata.contains_key("other"));
+        assert_eq!(form.refresh_pending, 2);
+    }
+
+    #[test]
+    fn refresh_is_ignored_while_one_is_running() {
+        let mut refreshing = form();
+        refreshing.mint = MINT.to_string();
+        assert!(refreshing.begin_refresh());
+        refreshing.metadata.insert(MINT.to_string(), None);
+        assert!(!refreshing.begin_refresh());
+        assert!(refreshing.metadata.contains_key(MINT));
+
+        let mut busy = form();
+        busy.loading = true;
+        assert!(!busy.begin_refresh());
+    }
+
+    #[test]
+    fn refresh_needs_a_wallet() {
+        let mut form = form();
+        form.wallet = WalletContext::default();
+        assert!(!form.begin_refresh());
+        assert_eq!(form.refresh_pending, 0);
+    }
+
+    #[test]
+    fn disconnecting_aborts_the_burn_and_clears_the_form() {
+        let mut form = form();
+        let aborted = Rc::new(Cell::new(false));
+        form.in_flight = Some(aborted.clone());
+        form.loading = true;
+        form.phase = BurnPhase::Signing;
+        form.mint = MINT.to_string();
+        form.amount_text = "1.5".to_string();
+        form.amount = "1.5".to_string();
+        form.token_account = Some(account(2_000_000, 6));
+        form.sol_balance = Some(1_000_000);
+
+        form.wallet = WalletContext::default();
+        form.disconnected();
+
+        assert!(aborted.get());
+        assert!(form.in_flight.is_none());
+        assert!(!form.loading);
+        assert_eq!(form.phase, BurnPhase::Idle);
+        assert!(form.mint.is_empty() && form.amount_text.is_empty() && form.amount.is_empty());
+        assert_eq!(form.token_account, None);
+        assert_eq!(form.sol_balance, None);
+        assert_eq!(form.status.as_deref(), Some("Wallet disconnected — please reconnect"));
+    }
+
+    #[test]
+    fn clear_resets_every_entered_field() {
+        let mut form = form();
+        form.mint = MINT.to_string();
+        form.amount_text = "1,5".to_string();
+        form.amount = "1.5".to_string();
+        form.amount_ambiguous = true;
+        form.burn_all = true;
+        form.amount_clipped = true;
+        form.memo = "gm".to_string();
+        form.decimals = Some(6);
+        form.decimals_unavailable = true;
+        form.compressed_asset = true;
+        form.holds_token = Some(true);
+        form.token_account = Some(account(2_000_000, 6));
+        form.status = Some("Invalid amount".to_string());
+
+        assert!(form.clear());
+        assert!(form.mint.is_empty());
+        assert!(form.amount_text.is_empty() && form.amount.is_empty());
+        assert!(!form.amount_ambiguous && !form.burn_all && !form.amount_clipped);
+        assert!(form.memo.is_empty());
+        assert_eq!(form.decimals, None);
+        assert!(!form.decimals_unavailable && !form.compressed_asset);
+        assert_eq!(form.holds_token, None);
+        assert_eq!(form.token_account, None);
+        assert_eq!(form.status, None);
+        assert!(form.pending_confirm.is_none());
+        assert!(form.focus_pending);
+    }
+
+    #[test]
+    fn clear_waits_for_a_running_burn() {
+        let mut form = form();
+        form.loading = true;
+        form.mint = MINT.to_string();
+        assert!(!form.clear());
+        assert_eq!(form.mint, MINT);
+    }
+
+    #[test]
+    fn interrupted_burn_round_trips() {
+        InterruptedBurn::save(&record(Cluster::Devnet), "1.5");
+        let (loaded, display_amount) = InterruptedBurn::load().unwrap();
+        assert_eq!(display_amount, "1.5");
+        // The symbol isn't stored; the rest comes back as it was.
+        assert_eq!(loaded, BurnRecord { symbol: None, ..record(Cluster::Devnet) });
+    }
+
+    #[test]
+    fn cleared_interrupted_burn_is_gone() {
+        InterruptedBurn::save(&record(Cluster::Mainnet), "1.5");
+        InterruptedBurn::clear();
+        assert_eq!(InterruptedBurn::load(), None);
+    }
+
+    #[test]
+    fn unreadable_interrupted_burns_are_dropped() {
+        InterruptedBurn::save(&BurnRecord { signature: "not a signature".to_string(), ..record(Cluster::Mainnet) }, "1");
+        assert_eq!(InterruptedBurn::load(), None);
+        assert_eq!(storage::get::<InterruptedBurn>(INTERRUPTED_BURN_KEY), Ok(None));
+
+        let unknown_cluster = InterruptedBurn {
+            signature: SIGNATURE.to_string(),
+            mint: MINT.to_string(),
+            amount: 1.0,
+            display_amount: "1".to_string(),
+            cluster: "localnet".to_string(),
+            timestamp: 0.0,
+            memo: None,
+        };
+        storage::set(INTERRUPTED_BURN_KEY, &unknown_cluster).unwrap();
+        assert_eq!(InterruptedBurn::load(), None);
+        assert_eq!(storage::get::<InterruptedBurn>(INTERRUPTED_BURN_KEY), Ok(None));
+    }
+
+    #[test]
+    fn interrupted_burns_saved_before_memos_still_load() {
+        let raw = format!(
+            r#"{{"signature":"{}","mint":"{}","amount":1.0,"display_amount":"1","cluster":"devnet","timestamp":0.0}}"#,
+            SIGNATURE, MINT
+        );
+        storage::set(INTERRUPTED_BURN_KEY, &storage::from_json::<InterruptedBurn>(&raw).unwrap()).unwrap();
+        let (loaded, _) = InterruptedBurn::load().unwrap();
+        assert_eq!(loaded.memo, None);
+        assert_eq!(loaded.cluster, Cluster::Devnet);
+    }
+
+    /// A form ready to burn 1 of the 2 tokens it holds.
+    fn burnable() -> BurnForm {
+        let mut form = form();
+        form.mint = MINT.to_string();
+        form.decimals = Some(6);
+        form.token_program = Some(TokenProgram::Legacy);
+        form.holds_token = Some(true);
+        form.token_account = Some(account(2_000_000, 6));
+        form.amount_text = "1".to_string();
+        form.amount = "1".to_string();
+        form
+    }
+
+    fn maintenance() -> ServiceStatus {
+        ServiceStatus { maintenance: true, message: "RPC issues".to_string(), ..ServiceStatus::default() }
+    }
+
+    #[test]
+    fn maintenance_blocks_opening_a_burn() {
+        let mut form = burnable();
+        form.service_changed(maintenance());
+        assert!(form.open_confirm());
+        assert!(form.pending_confirm.is_none());
+        assert_eq!(form.status.as_deref(), Some(AmountError::Maintenance.message()));
+    }
+
+    #[test]
+    fn maintenance_closes_an_open_burn() {
+        let mut form = burnable();
+        assert!(form.open_confirm());
+        assert!(form.pending_confirm.is_some());
+
+        form.service_changed(maintenance());
+        assert!(form.pending_confirm.is_none());
+        assert!(form.take_confirmed().is_none());
+    }
+
+    #[test]
+    fn normal_service_lets_the_burn_through() {
+        let mut form = burnable();
+        assert!(form.open_confirm());
+        form.service_changed(ServiceStatus::default());
+        let pending = form.take_confirmed().unwrap();
+        assert_eq!(pending.request.amount, 1_000_000);
+        assert!(form.pending_confirm.is_none());
+    }
+
+    #[test]
+    fn max_burns_the_exact_raw_balance() {
+        let mut form = burnable();
+        // More digits than an f64 holds: 9007199.254740993 reads back as
+        // 9007199.254740992.
+        let raw = 9_007_199_254_740_993;
+        form.decimals = Some(9);
+        form.token_account = Some(account(raw, 9));
+        form.fill_max();
+        assert!(form.burn_all);
+        assert_eq!(form.amount, "9007199.254740993");
+
+        assert!(form.open_confirm());
+        let pending = form.pending_confirm.unwrap();
+        assert_eq!(pending.request.amount, raw);
+        assert!(pending.entire_balance && pending.requires_challenge);
+    }
+
+    #[test]
+    fn max_without_a_balance_fills_nothing() {
+        let mut form = form();
+        form.fill_max();
+        assert!(!form.burn_all);
+        assert!(form.amount.is_empty());
+    }
+
+    #[test]
+    fn burning_the_whole_balance_waits_for_the_typed_challenge() {
+        let mut form = burnable();
+        form.fill_max();
+        assert!(form.open_confirm());
+        assert!(form.take_confirmed().is_none());
+        assert!(form.pending_confirm.is_some());
+
+        form.challenge_passed = true;
+        assert_eq!(form.take_confirmed().unwrap().request.amount, 2_000_000);
+    }
+
+    #[test]
+    fn max_shows_the_tokens_decimals_but_submits_the_exact_amount() {
+        let mut form = burnable();
+        form.separators = NumberSeparators { decimal: ',', grouping: Some('.') };
+        form.token_account = Some(account(99_999_999, 6));
+        form.fill_max();
+        assert_eq!(form.amount_text, "99,999999");
+        assert_eq!(form.amount, "99.999999");
+
+        assert!(form.open_confirm());
+        form.challenge_passed = true;
+        let pending = form.take_confirmed().unwrap();
+        assert_eq!(pending.display_amount, "99.999999");
+        assert_eq!(pending.request.amount, 99_999_999);
+    }
+
+    #[test]
+    fn overlapping_confirm_triggers_open_one_dialog() {
+        let mut form = burnable();
+        form.fill_max();
+        assert!(form.open_confirm());
+        form.challenge_passed = true;
+        let opened = form.pending_confirm.clone();
+
+        // Enter and a click landing together, with the amount changed meanwhile.
+        form.amount = "1".to_string();
+        form.burn_all = false;
+        assert!(!form.open_confirm());
+        assert_eq!(form.pending_confirm, opened);
+        assert!(form.challenge_passed);
+    }
+
+    #[test]
+    fn a_confirmed_burn_is_taken_once() {
+        let mut form = burnable();
+        assert!(form.open_confirm());
+        assert!(form.take_confirmed().is_some());
+        assert!(form.take_confirmed().is_none());
+    }
+
+    #[test]
+    fn a_second_submit_while_the_first_confirms_is_blocked() {
+        let burns = InFlightBurns::default();
+        let mut first = burnable();
+        first.burns_in_flight = burns.clone();
+        assert!(first.open_confirm());
+        let pending = first.take_confirmed().unwrap();
+        burns.start(&pending.request.mint);
+
+        let mut second = burnable();
+        second.burns_in_flight = burns.clone();
+        assert!(second.open_confirm());
+        assert!(second.pending_confirm.is_none());
+        assert_eq!(second.status.as_deref(), Some(AmountError::AlreadyInFlight.message()));
+
+        burns.finish(MINT);
+        assert!(second.open_confirm());
+        assert!(second.pending_confirm.is_some());
+    }
+}
@@ -0,0 +1,43 @@
+use yew::prelude::*;
+use web_sys::HtmlInputElement;
+use wasm_bindgen::JsCast;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    /// Text the user has to type, compared case-insensitively.
+    pub phrase: AttrValue,
+    /// Called with whether the current input matches `phrase`.
+    pub on_change: Callback<bool>,
+}
+
+/// Asks the user to type a phrase before a risky action is allowed.
+pub struct ConfirmChallenge;
+
+impl Component for ConfirmChallenge {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let phrase = ctx.props().phrase.clone();
+        let oninput = {
+            let phrase = phrase.clone();
+            ctx.props().on_change.reform(move |e: InputEvent| {
+                let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+                input.value().trim().eq_ignore_ascii_case(&phrase)
+            })
+        };
+
+        html! {
+            <div class="input-group confirm-challenge">
+                <label for="confirm-challenge">
+                    {"Type "}<strong>{phrase.clone()}</strong>{" to confirm:"}
+                </label>
+                <input type="text" id="confirm-challenge" autocomplete="off" spellcheck="false" {oninput} />
+            </div>
+        }
+    }
+}
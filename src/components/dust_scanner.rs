@@ -0,0 +1,906 @@
+use std::collections::HashSet;
+use yew::prelude::*;
+use web_sys::{Element, HtmlElement, HtmlInputElement};
+use wasm_bindgen::JsCast;
+use crate::api::ServiceStatus;
+use crate::components::address_display::{AddressDisplay, AddressKind};
+use crate::components::confirm_challenge::ConfirmChallenge;
+use crate::components::history::BurnRecord;
+use crate::components::modal::Modal;
+use crate::context::{DecimalsCache, InFlightBurns, Settings, WalletContext};
+use crate::rpc::{self, TokenAccount};
+use crate::transaction::{self, BurnRequest, TokenProgram, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+use crate::utils::{is_valid_address, truncate_middle, Cluster, DEFAULT_COMPUTE_UNIT_LIMIT, MAX_ADDRESS_LEN};
+
+/// Burns per transaction, keeping each one well under the size limit.
+const BATCH_SIZE: usize = 8;
+/// Phrase typed to release a bulk burn.
+const BULK_CHALLENGE_PHRASE: &str = "BURN ALL";
+
+/// Where the user is in confirming a bulk burn, the riskiest action in the
+/// app: first a review of every balance, then a typed phrase.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Interlock {
+    Review,
+    Challenge { passed: bool },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum DustOutcome {
+    /// Burned in the transaction with this signature.
+    Burned(String),
+    /// Dry run: the batch would have gone through using this many compute
+    /// units.
+    Simulated(u64),
+    Failed(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct DustResult {
+    account: TokenAccount,
+    outcome: DustOutcome,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub on_burn: Callback<BurnRecord>,
+}
+
+/// Lists the wallet's token accounts holding less than a threshold and burns
+/// them all at once.
+pub struct DustScanner {
+    threshold: String,
+    /// Address to inspect read-only while no wallet is connected.
+    watch_address: String,
+    tokens: Option<Vec<TokenAccount>>,
+    loading: bool,
+    /// Bumped by every scan and by anything that makes a running scan
+    /// stale (another address, wallet or cluster), so late results for the
+    /// old one are dropped.
+    scan_generation: u32,
+    interlock: Option<Interlock>,
+    /// Token accounts the user deselected from the dust list.
+    excluded: HashSet<String>,
+    /// Row of the dust list that takes arrow-key focus.
+    focused: usize,
+    /// Move DOM focus to `focused` after the next render.
+    focus_row: bool,
+    list_ref: NodeRef,
+    burning: bool,
+    results: Vec<DustResult>,
+    error: Option<String>,
+    wallet: WalletContext,
+    cluster: Cluster,
+    service: ServiceStatus,
+    settings: Settings,
+    burns_in_flight: InFlightBurns,
+    _wallet_handle: Option<ContextHandle<WalletContext>>,
+    _cache_handle: Option<ContextHandle<DecimalsCache>>,
+    _service_handle: Option<ContextHandle<ServiceStatus>>,
+    _settings_handle: Option<ContextHandle<Settings>>,
+}
+
+pub enum Msg {
+    Scan,
+    Scanned(u32, Result<Vec<TokenAccount>, String>),
+    UpdateThreshold(String),
+    UpdateWatchAddress(String),
+    FocusRow(usize),
+    RowFocused(usize),
+    ToggleRow(String),
+    RequestBurn,
+    ReviewAccepted,
+    ChallengeChanged(bool),
+    CancelBurn,
+    ConfirmBurn,
+    /// A sent batch settled, on the cluster it was sent to.
+    BatchBurned(Cluster, Vec<TokenAccount>, Result<String, String>),
+    BatchSimulated(Vec<TokenAccount>, Result<u64, String>),
+    BurnFinished,
+    WalletChanged(WalletContext),
+    CacheChanged(DecimalsCache),
+    ServiceChanged(ServiceStatus),
+    SettingsChanged(Settings),
+}
+
+impl Component for DustScanner {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (wallet, wallet_handle) = match ctx
+            .link()
+            .context::<WalletContext>(ctx.link().callback(Msg::WalletChanged))
+        {
+            Some((wallet, handle)) => (wallet, Some(handle)),
+            None => (WalletContext::default(), None),
+        };
+        let (cluster, cache_handle) = match ctx
+            .link()
+            .context::<DecimalsCache>(ctx.link().callback(Msg::CacheChanged))
+        {
+            Some((cache, handle)) => (cache.cluster(), Some(handle)),
+            None => (Cluster::default(), None),
+        };
+        let (service, service_handle) = match ctx
+            .link()
+            .context::<ServiceStatus>(ctx.link().callback(Msg::ServiceChanged))
+        {
+            Some((service, handle)) => (service, Some(handle)),
+            None => (ServiceStatus::default(), None),
+        };
+        let (settings, settings_handle) = match ctx
+            .link()
+            .context::<Settings>(ctx.link().callback(Msg::SettingsChanged))
+        {
+            Some((settings, handle)) => (settings, Some(handle)),
+            None => (Settings::default(), None),
+        };
+        let burns_in_flight = ctx
+            .link()
+            .context::<InFlightBurns>(Callback::noop())
+            .map(|(burns, _)| burns)
+            .unwrap_or_default();
+
+        Self {
+            _wallet_handle: wallet_handle,
+            _cache_handle: cache_handle,
+            _service_handle: service_handle,
+            _settings_handle: settings_handle,
+            ..Self::new(wallet, cluster, service, settings, burns_in_flight)
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Scan => {
+                let owner = match self.scan_target() {
+                    Some(owner) => owner,
+                    None => return false,
+                };
+                self.loading = true;
+                self.error = None;
+                let generation = self.next_scan();
+                let cluster = self.cluster;
+                let link = ctx.link().clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let mut tokens = Vec::new();
+                    for program_id in [TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID] {
+                        match rpc::get_token_accounts(cluster, &owner, program_id).await {
+                            Ok(accounts) => tokens.extend(accounts),
+                            Err(_) => {
+                                let error = Err("Could not load token accounts".to_string());
+                                link.send_message(Msg::Scanned(generation, error));
+                                return;
+                            }
+                        }
+                    }
+                    link.send_message(Msg::Scanned(generation, Ok(tokens)));
+                });
+                true
+            }
+            Msg::Scanned(generation, result) => {
+                if generation != self.scan_generation {
+                    return false;
+                }
+                self.loading = false;
+                match result {
+                    Ok(tokens) => {
+                        self.tokens = Some(tokens);
+                        self.excluded.clear();
+                        self.focused = 0;
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+                true
+            }
+            Msg::UpdateWatchAddress(address) => {
+                self.watch_address = address.trim().to_string();
+                self.next_scan();
+                self.loading = false;
+                self.tokens = None;
+                self.error = None;
+                self.excluded.clear();
+                self.results.clear();
+                true
+            }
+            Msg::UpdateThreshold(threshold) => {
+                self.threshold = threshold;
+                self.interlock = None;
+                true
+            }
+            Msg::FocusRow(index) => {
+                self.focused = index;
+                self.focus_row = true;
+                true
+            }
+            Msg::RowFocused(index) => {
+                let changed = index != self.focused;
+                self.focused = index;
+                changed
+            }
+            Msg::ToggleRow(address) => {
+                if !self.excluded.remove(&address) {
+                    self.excluded.insert(address);
+                }
+                self.interlock = None;
+                true
+            }
+            Msg::RequestBurn => self.request_burn(),
+            Msg::ReviewAccepted => self.review_accepted(),
+            Msg::ChallengeChanged(passed) => self.challenge_changed(passed),
+            Msg::CancelBurn => {
+                self.interlock = None;
+                true
+            }
+            Msg::ConfirmBurn => {
+                let (owner, dust) = match self.release_batch() {
+                    Some(batch) => batch,
+                    None => return false,
+                };
+                let cluster = self.cluster;
+                let link = ctx.link().clone();
+                if self.settings.dry_run {
+                    // Simulated batch by batch, exactly as they'd be sent, but
+                    // nothing is signed and no mint is marked in flight.
+                    wasm_bindgen_futures::spawn_local(async move {
+                        for batch in dust.chunks(BATCH_SIZE) {
+                            let requests = dust_requests(&owner, batch);
+                            let result = transaction::simulate_burns(cluster, &requests).await;
+                            link.send_message(Msg::BatchSimulated(batch.to_vec(), result));
+                        }
+                        link.send_message(Msg::BurnFinished);
+                    });
+                    return true;
+                }
+
+                for account in &dust {
+                    self.burns_in_flight.start(&account.mint);
+                }
+                let burns = self.burns_in_flight.clone();
+                let settings = self.settings.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    for batch in dust.chunks(BATCH_SIZE) {
+                        let requests = dust_requests(&owner, batch);
+                        // A batch counts as burned, and its mints free to burn
+                        // again, only once it's confirmed.
+                        let result = match transaction::submit_burns(cluster, &requests).await {
+                            Ok(signature) => transaction::confirm_signature(
+                                cluster,
+                                &signature,
+                                settings.confirm_poll_interval_ms,
+                                settings.confirm_max_attempts,
+                                settings.slow_confirm_seconds,
+                                || {},
+                            )
+                            .await
+                            .map(|()| signature),
+                            Err(error) => Err(error),
+                        };
+                        for account in batch {
+                            burns.finish(&account.mint);
+                        }
+                        link.send_message(Msg::BatchBurned(cluster, batch.to_vec(), result));
+                    }
+                    link.send_message(Msg::BurnFinished);
+                });
+                true
+            }
+            Msg::BatchBurned(cluster, accounts, outcome) => {
+                // Confirmed burns go into history under the cluster they were
+                // sent on, even if the user has switched since; the results
+                // list only shows the current cluster's.
+                let current = cluster == self.cluster;
+                for account in accounts {
+                    if let Ok(signature) = &outcome {
+                        ctx.props().on_burn.emit(BurnRecord {
+                            timestamp: js_sys::Date::now(),
+                            mint: account.mint.clone(),
+                            symbol: None,
+                            amount: account.ui_amount,
+                            signature: signature.clone(),
+                            cluster,
+                            memo: None,
+                        });
+                    }
+                    if !current {
+                        continue;
+                    }
+                    let outcome = match &outcome {
+                        Ok(signature) => DustOutcome::Burned(signature.clone()),
+                        Err(error) => DustOutcome::Failed(error.clone()),
+                    };
+                    self.results.push(DustResult { account, outcome });
+                }
+                true
+            }
+            Msg::BatchSimulated(accounts, outcome) => {
+                for account in accounts {
+                    let outcome = match &outcome {
+                        Ok(units) => DustOutcome::Simulated(*units),
+                        Err(error) => DustOutcome::Failed(error.clone()),
+                    };
+                    self.results.push(DustResult { account, outcome });
+                }
+                true
+            }
+            Msg::BurnFinished => {
+                self.burning = false;
+                ctx.link().send_message(Msg::Scan);
+                true
+            }
+            Msg::WalletChanged(wallet) => {
+                self.wallet = wallet;
+                self.next_scan();
+                self.loading = false;
+                self.tokens = None;
+                self.interlock = None;
+                self.excluded.clear();
+                self.results.clear();
+                true
+            }
+            Msg::CacheChanged(cache) => {
+                self.cluster = cache.cluster();
+                self.next_scan();
+                self.loading = false;
+                self.tokens = None;
+                self.interlock = None;
+                self.excluded.clear();
+                self.results.clear();
+                true
+            }
+            Msg::ServiceChanged(service) => {
+                if service.maintenance {
+                    self.interlock = None;
+                }
+                self.service = service;
+                true
+            }
+            Msg::SettingsChanged(settings) => {
+                self.settings = settings;
+                true
+            }
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if !std::mem::take(&mut self.focus_row) {
+            return;
+        }
+        let row = self
+            .list_ref
+            .cast::<Element>()
+            .and_then(|list| list.children().item(self.focused as u32))
+            .and_then(|row| row.dyn_into::<HtmlElement>().ok());
+        if let Some(row) = row {
+            let _ = row.focus();
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let onscan = ctx.link().callback(|_| Msg::Scan);
+        let onthreshold = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+            Msg::UpdateThreshold(input.value())
+        });
+        let onburn = ctx.link().callback(|_| Msg::RequestBurn);
+        let onwatch = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
+            Msg::UpdateWatchAddress(input.value())
+        });
+        let watching = self.watching();
+        let dust = self.dust();
+        let selected = self.selected();
+        let busy = self.loading || self.burning;
+
+        html! {
+            <div class="dust-scanner">
+                <h2>{"Dust Cleanup"}</h2>
+                if watching {
+                    <div class="input-group">
+                        <label for="watch-address">{"Wallet address to inspect:"}</label>
+                        <input
+                            type="text"
+                            id="watch-address"
+                            value={self.watch_address.clone()}
+                            oninput={onwatch}
+                            disabled={busy}
+                            placeholder="Any Solana wallet address"
+                            maxlength="64"
+                            spellcheck="false"
+                        />
+                        <div class="input-hint">
+                            {"Watch mode is read-only: you can see its tokens and dust, but burning needs the wallet connected."}
+                        </div>
+                        if !self.watch_address.is_empty() && self.scan_target().is_none() {
+                            <div class="input-hint error">{"That isn't a valid wallet address."}</div>
+                        }
+                    </div>
+                }
+                <div class="input-group">
+                    <label for="dust-threshold">{"Burn balances below:"}</label>
+                    <input
+                        type="number"
+                        id="dust-threshold"
+                        value={self.threshold.clone()}
+                        oninput={onthreshold}
+                        disabled={busy}
+                        min="0"
+                        step="any"
+                    />
+                </div>
+                <button type="button" onclick={onscan} disabled={busy || self.scan_target().is_none()}>
+                    if self.loading {
+                        {"Scanning..."}
+                    } else if watching {
+                        {"Scan Address"}
+                    } else {
+                        {"Scan Wallet"}
+                    }
+                </button>
+                if let Some(error) = &self.error {
+                    <div class="status-message error">{error}</div>
+                }
+                if self.loading && self.tokens.is_none() {
+                    <div class="empty-state" role="status">{"Looking up your token accounts…"}</div>
+                } else if self.holds_nothing() {
+                    <div class="empty-state">
+                        <p>{"This wallet doesn't hold any SPL tokens, so there's nothing to burn."}</p>
+                        <p class="input-hint">
+                            {"Tokens show up here once they arrive. If you expected some, check that the right wallet and cluster are selected."}
+                        </p>
+                    </div>
+                } else if self.tokens.is_some() {
+                    if dust.is_empty() {
+                        <div class="empty-state">{"No balances below the threshold."}</div>
+                    }
+                    {self.dust_list(ctx, &dust)}
+                    if watching {
+                        <div class="input-hint">
+                            {format!("{} dust balance(s) found. Connect this wallet to burn them.", dust.len())}
+                        </div>
+                    } else {
+                        <button type="button" onclick={onburn} disabled={busy || self.service.maintenance || selected.is_empty()}>
+                            if self.settings.dry_run {
+                                {format!("Simulate burning selected dust ({})", selected.len())}
+                            } else {
+                                {format!("Burn selected dust ({})", selected.len())}
+                            }
+                        </button>
+                    }
+                }
+                if let Some(interlock) = self.interlock {
+                    {self.interlock_dialog(ctx, interlock, &selected)}
+                }
+                if !self.results.is_empty() {
+                    <ul class="dust-results">
+                        { for self.results.iter().map(|result| match &result.outcome {
+                            DustOutcome::Burned(signature) => html! {
+                                <li class="success">
+                                    {format!("Burned {} {} — ", result.account.ui_amount, truncate_middle(&result.account.mint, MAX_ADDRESS_LEN))}
+                                    <AddressDisplay
+                                        address={signature.clone()}
+                                        kind={AddressKind::Signature}
+                                        cluster={self.cluster}
+                                    />
+                                </li>
+                            },
+                            DustOutcome::Simulated(units) => html! {
+                                <li class="success">
+                                    {format!(
+                                        "Dry run: burning {} {} would go through (batch uses {} compute units). Nothing was sent.",
+                                        result.account.ui_amount,
+                                        truncate_middle(&result.account.mint, MAX_ADDRESS_LEN),
+                                        units
+                                    )}
+                                </li>
+                            },
+                            DustOutcome::Failed(error) => html! {
+                                <li class="error">{format!("{}: {}", truncate_middle(&result.account.mint, MAX_ADDRESS_LEN), error)}</li>
+                            },
+                        }) }
+                    </ul>
+                }
+            </div>
+        }
+    }
+}
+
+impl DustScanner {
+    /// An empty scanner over the given contexts, not subscribed to any of them.
+    fn new(
+        wallet: WalletContext,
+        cluster: Cluster,
+        service: ServiceStatus,
+        settings: Settings,
+        burns_in_flight: InFlightBurns,
+    ) -> Self {
+        Self {
+            threshold: "1".to_string(),
+            watch_address: String::new(),
+            tokens: None,
+            loading: false,
+            scan_generation: 0,
+            interlock: None,
+            excluded: HashSet::new(),
+            focused: 0,
+            focus_row: false,
+            list_ref: NodeRef::default(),
+            burning: false,
+            results: Vec::new(),
+            error: None,
+            wallet,
+            cluster,
+            service,
+            settings,
+            burns_in_flight,
+            _wallet_handle: None,
+            _cache_handle: None,
+            _service_handle: None,
+            _settings_handle: None,
+        }
+    }
+
+    /// Opens the interlock at its review step.
+    fn request_burn(&mut self) -> bool {
+        // A second trigger while the interlock is open would restart it from
+        // review; ignore it instead.
+        if self.interlock.is_some()
+            || self.burning
+            || self.watching()
+            || self.service.maintenance
+            || self.selected().is_empty()
+        {
+            return false;
+        }
+        self.interlock = Some(Interlock::Review);
+        true
+    }
+
+    fn review_accepted(&mut self) -> bool {
+        if self.interlock != Some(Interlock::Review) {
+            return false;
+        }
+        self.interlock = Some(Interlock::Challenge { passed: false });
+        true
+    }
+
+    fn challenge_changed(&mut self, passed: bool) -> bool {
+        if !matches!(self.interlock, Some(Interlock::Challenge { .. })) {
+            return false;
+        }
+        self.interlock = Some(Interlock::Challenge { passed });
+        true
+    }
+
+    /// Closes the interlock and hands over the owner and the accounts to
+    /// burn, but only from its final step, fully passed.
+    fn release_batch(&mut self) -> Option<(String, Vec<TokenAccount>)> {
+        if self.interlock != Some(Interlock::Challenge { passed: true }) || self.service.maintenance {
+            return None;
+        }
+        let owner = self.wallet.public_key.clone()?;
+        // Mints the burn form is still burning are left out rather than
+        // burned twice.
+        let (busy, dust): (Vec<TokenAccount>, Vec<TokenAccount>) = self
+            .selected()
+            .into_iter()
+            .cloned()
+            .partition(|account| self.burns_in_flight.contains(&account.mint));
+        self.interlock = None;
+        self.burning = true;
+        self.results = busy
+            .into_iter()
+            .map(|account| DustResult {
+                account,
+                outcome: DustOutcome::Failed("Skipped: a burn of this token is still awaiting confirmation".to_string()),
+            })
+            .collect();
+        Some((owner, dust))
+    }
+
+    /// Dust rows as a listbox: arrow keys, Home and End move between rows,
+    /// Enter or Space (or a click) toggles whether a row gets burned. Only
+    /// the focused row is in the tab order, so Tab leaves the list.
+    fn dust_list(&self, ctx: &Context<Self>, dust: &[&TokenAccount]) -> Html {
+        let focused = self.focused.min(dust.len().saturating_sub(1));
+        let addresses: Vec<String> = dust.iter().map(|account| account.address.clone()).collect();
+        let onkeydown = ctx.link().batch_callback(move |e: KeyboardEvent| {
+            // Leave keys pressed on the links and buttons inside a row alone.
+            let on_row = e
+                .target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+                .map_or(false, |target| target.tag_name() == "LI");
+            if !on_row || addresses.is_empty() {
+                return None;
+            }
+            let msg = match e.key().as_str() {
+                "ArrowDown" => Msg::FocusRow((focused + 1).min(addresses.len() - 1)),
+                "ArrowUp" => Msg::FocusRow(focused.saturating_sub(1)),
+                "Home" => Msg::FocusRow(0),
+                "End" => Msg::FocusRow(addresses.len() - 1),
+                "Enter" | " " => Msg::ToggleRow(addresses[focused].clone()),
+                _ => return None,
+            };
+            e.prevent_default();
+            Some(msg)
+        });
+
+        html! {
+            <ul
+                class="dust-list"
+                role="listbox"
+                aria-label="Dust balances"
+                aria-multiselectable="true"
+                ref={self.list_ref.clone()}
+                {onkeydown}
+            >
+                { for dust.iter().enumerate().map(|(index, account)| {
+                    let address = account.address.clone();
+                    let selected = !self.excluded.contains(&account.address);
+                    let onclick = ctx.link().batch_callback(move |e: MouseEvent| {
+                        let on_row = e
+                            .target()
+                            .and_then(|target| target.dyn_into::<Element>().ok())
+                            .map_or(false, |target| target.tag_name() == "LI");
+                        on_row.then(|| Msg::ToggleRow(address.clone()))
+                    });
+                    let onfocus = ctx.link().callback(move |_: FocusEvent| Msg::RowFocused(index));
+                    html! {
+                        <li
+                            class={if selected { "dust-row selected" } else { "dust-row" }}
+                            role="option"
+                            aria-selected={selected.to_string()}
+                            tabindex={if index == focused { "0" } else { "-1" }}
+                            {onclick}
+                            {onfocus}
+                        >
+                            <AddressDisplay
+                                address={account.mint.clone()}
+                                kind={AddressKind::Mint}
+                                cluster={self.cluster}
+                            />
+                            {format!(" — {}", account.ui_amount)}
+                        </li>
+                    }
+                }) }
+            </ul>
+        }
+    }
+
+    /// Without a connected wallet the scanner only looks: it lists the
+    /// watched address's dust but never burns it.
+    fn watching(&self) -> bool {
+        !self.wallet.connected
+    }
+
+    /// Starts a new scan generation, making any running scan stale.
+    fn next_scan(&mut self) -> u32 {
+        self.scan_generation = self.scan_generation.wrapping_add(1);
+        self.scan_generation
+    }
+
+    /// Whose token accounts a scan reads: the connected wallet's, or the
+    /// watched address when it's valid.
+    fn scan_target(&self) -> Option<String> {
+        if self.watching() {
+            Some(self.watch_address.clone()).filter(|address| is_valid_address(address))
+        } else {
+            self.wallet.public_key.clone()
+        }
+    }
+
+    /// Dust rows the user hasn't deselected; what a bulk burn destroys.
+    fn selected(&self) -> Vec<&TokenAccount> {
+        self.dust()
+            .into_iter()
+            .filter(|account| !self.excluded.contains(&account.address))
+            .collect()
+    }
+
+    /// The two-step bulk burn confirmation: review the exact balances, then
+    /// type the phrase.
+    fn interlock_dialog(&self, ctx: &Context<Self>, interlock: Interlock, dust: &[&TokenAccount]) -> Html {
+        let oncancel = ctx.link().callback(|_| Msg::CancelBurn);
+        let cancel = html! {
+            <button type="button" class="secondary-button" onclick={oncancel.reform(|_: MouseEvent| ())}>
+                {"Cancel"}
+            </button>
+        };
+
+        html! {
+            <Modal title="Burn all dust" on_cancel={oncancel.clone()}>
+                <p>{format!("These {} token balances will be permanently burned:", dust.len())}</p>
+                <ul class="dust-confirm">
+                    { for dust.iter().map(|account| html! {
+                        <li>{format!("{} {}", account.ui_amount, truncate_middle(&account.mint, MAX_ADDRESS_LEN))}</li>
+                    }) }
+                </ul>
+                if self.settings.dry_run {
+                    <p class="dry-run-badge">{"Dry run: these burns will only be simulated."}</p>
+                } else {
+                    <p class="error">{"This cannot be undone."}</p>
+                }
+                if let Interlock::Challenge { passed } = interlock {
+                    <ConfirmChallenge
+                        phrase={BULK_CHALLENGE_PHRASE}
+                        on_change={ctx.link().callback(Msg::ChallengeChanged)}
+                    />
+                    <div class="modal-actions">
+                        {cancel}
+                        <button
+                            type="button"
+                            class="danger-button"
+                            onclick={ctx.link().callback(|_| Msg::ConfirmBurn)}
+                            disabled={!passed}
+                        >
+                            if self.settings.dry_run {
+                                {format!("Simulate {} burns", dust.len())}
+                            } else {
+                                {format!("Burn {} balances", dust.len())}
+                            }
+                        </button>
+                    </div>
+                } else {
+                    <div class="modal-actions">
+                        {cancel}
+                        <button type="button" onclick={ctx.link().callback(|_| Msg::ReviewAccepted)}>
+                            {"I've checked this list"}
+                        </button>
+                    </div>
+                }
+            </Modal>
+        }
+    }
+
+    /// A scan finished and found no account with a non-zero balance.
+    fn holds_nothing(&self) -> bool {
+        self.tokens
+            .as_ref()
+            .map_or(false, |tokens| tokens.iter().all(|account| account.amount == 0))
+    }
+
+    /// Scanned accounts holding something, but less than the threshold.
+    /// Frozen ones are left out: a single frozen account would fail its
+    /// whole batch.
+    fn dust(&self) -> Vec<&TokenAccount> {
+        let threshold = match self.threshold.parse::<f64>() {
+            Ok(threshold) if threshold > 0.0 => threshold,
+            _ => return Vec::new(),
+        };
+        self.tokens
+            .iter()
+            .flatten()
+            .filter(|account| account.amount > 0 && account.ui_amount < threshold && !account.frozen)
+            .collect()
+    }
+}
+
+/// One burn of each account's whole balance, for a single transaction.
+fn dust_requests(owner: &str, batch: &[TokenAccount]) -> Vec<BurnRequest> {
+    batch
+        .iter()
+        .map(|account| BurnRequest {
+            owner: owner.to_string(),
+            mint: account.mint.clone(),
+            amount: account.amount,
+            decimals: account.decimals,
+            token_program: TokenProgram::from_owner(&account.program).unwrap_or_default(),
+            token_account: Some(account.address.clone()),
+            priority_fee_micro_lamports: 0,
+            compute_unit_limit: DEFAULT_COMPUTE_UNIT_LIMIT,
+            memo: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNER: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const BONK: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+    fn account(address: &str, mint: &str, amount: u64) -> TokenAccount {
+        TokenAccount {
+            address: address.to_string(),
+            mint: mint.to_string(),
+            program: TOKEN_PROGRAM_ID.to_string(),
+            amount,
+            decimals: 6,
+            ui_amount: amount as f64 / 1e6,
+            frozen: false,
+        }
+    }
+
+    /// A connected scanner that found two dust balances below the default
+    /// threshold of 1.
+    fn scanned() -> DustScanner {
+        let wallet = WalletContext { connected: true, public_key: Some(OWNER.to_string()) };
+        let mut scanner = DustScanner::new(
+            wallet,
+            Cluster::Mainnet,
+            ServiceStatus::default(),
+            Settings::default(),
+            InFlightBurns::default(),
+        );
+        scanner.tokens = Some(vec![
+            account("Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr", USDC, 250_000),
+            account("7UX2i7SucgLMQcfZ75s3VXmZZY4YRUyJN9X1RgfMoDUi", BONK, 10),
+        ]);
+        scanner
+    }
+
+    #[test]
+    fn batch_runs_only_after_review_and_a_passed_challenge() {
+        let mut scanner = scanned();
+        assert!(scanner.release_batch().is_none());
+
+        assert!(scanner.request_burn());
+        assert!(scanner.release_batch().is_none());
+
+        assert!(scanner.review_accepted());
+        assert!(scanner.release_batch().is_none());
+
+        assert!(scanner.challenge_changed(true));
+        assert!(scanner.challenge_changed(false));
+        assert!(scanner.release_batch().is_none());
+        assert!(!scanner.burning);
+
+        assert!(scanner.challenge_changed(true));
+        let (owner, dust) = scanner.release_batch().unwrap();
+        assert_eq!(owner, OWNER);
+        assert_eq!(dust.len(), 2);
+        assert!(scanner.burning);
+        assert_eq!(scanner.interlock, None);
+        // Released once; confirming again sends nothing more.
+        assert!(scanner.release_batch().is_none());
+    }
+
+    #[test]
+    fn steps_cannot_be_skipped() {
+        let mut scanner = scanned();
+        assert!(!scanner.review_accepted());
+        assert!(!scanner.challenge_changed(true));
+        assert_eq!(scanner.interlock, None);
+
+        assert!(scanner.request_burn());
+        assert!(!scanner.challenge_changed(true));
+        assert_eq!(scanner.interlock, Some(Interlock::Review));
+    }
+
+    #[test]
+    fn a_second_trigger_does_not_restart_the_interlock() {
+        let mut scanner = scanned();
+        assert!(scanner.request_burn());
+        assert!(scanner.review_accepted());
+        assert!(!scanner.request_burn());
+        assert_eq!(scanner.interlock, Some(Interlock::Challenge { passed: false }));
+    }
+
+    #[test]
+    fn watching_or_maintenance_never_opens_the_interlock() {
+        let mut watching = scanned();
+        watching.wallet = WalletContext::default();
+        assert!(!watching.request_burn());
+
+        let mut paused = scanned();
+        paused.service = ServiceStatus { maintenance: true, ..ServiceStatus::default() };
+        assert!(!paused.request_burn());
+        assert_eq!(paused.interlock, None);
+    }
+
+    #[test]
+    fn mints_still_burning_elsewhere_are_skipped() {
+        let mut scanner = scanned();
+        scanner.burns_in_flight.start(USDC);
+        assert!(scanner.request_burn() && scanner.review_accepted() && scanner.challenge_changed(true));
+
+        let (_, dust) = scanner.release_batch().unwrap();
+        assert_eq!(dust.iter().map(|account| account.mint.as_str()).collect::<Vec<_>>(), [BONK]);
+        assert_eq!(scanner.results.len(), 1);
+        assert_eq!(scanner.results[0].account.mint, USDC);
+        assert!(matches!(scanner.results[0].outcome, DustOutcome::Failed(_)));
+    }
+}
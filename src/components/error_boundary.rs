@@ -0,0 +1,66 @@
+use yew::prelude::*;
+use wasm_bindgen::JsCast;
+
+const BOUNDARY_ATTR: &str = "data-error-boundary";
+
+/// Set on `<body>` once the app has stopped, to grey out controls that no
+/// longer respond.
+const STOPPED_CLASS: &str = "app-stopped";
+
+/// Written straight into the DOM: after a panic the WASM instance is gone and
+/// runs no event handlers, so the fallback only uses an inline reload.
+const FALLBACK_HTML: &str = r#"<div class="error-fallback" role="alert">
+    <p>Something went wrong and the app stopped. The rest of the page stays visible but no longer responds.</p>
+    <p>A burn you already approved in your wallet may still go through; check the explorer before retrying.</p>
+    <button type="button" onclick="window.location.reload()">Reload</button>
+</div>"#;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Marks the subtree that the panic hook replaces with a recovery message.
+///
+/// A Rust panic in WASM ends the whole module, not just the component that
+/// panicked, so there's no recovering in place: everything rendered outside
+/// the boundary (header, cluster picker) stays on screen for reference but
+/// is dead, and is greyed out to say so. Getting going again takes a
+/// reload. Errors that don't panic are shown by each component itself.
+pub struct ErrorBoundary;
+
+impl Component for ErrorBoundary {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div data-error-boundary="">
+                { for ctx.props().children.iter() }
+            </div>
+        }
+    }
+}
+
+/// Called from the panic hook.
+pub fn show_fallback() {
+    let document = match web_sys::window().and_then(|window| window.document()) {
+        Some(document) => document,
+        None => return,
+    };
+    if let Some(body) = document.body() {
+        let _ = body.class_list().add_1(STOPPED_CLASS);
+    }
+    if let Ok(boundaries) = document.query_selector_all(&format!("[{}]", BOUNDARY_ATTR)) {
+        for i in 0..boundaries.length() {
+            if let Some(element) = boundaries.item(i).and_then(|node| node.dyn_into::<web_sys::Element>().ok()) {
+                element.set_inner_html(FALLBACK_HTML);
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::BTreeMap;
+use gloo_timers::callback::{Interval, Timeout};
+use yew::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+use crate::components::address_display::{AddressDisplay, AddressKind};
+use crate::utils::{explorer_url, format_relative_time, Cluster, Explorer};
+
+const CHART_WIDTH: f64 = 400.0;
+const CHART_HEIGHT: f64 = 160.0;
+const LABEL_HEIGHT: f64 = 30.0;
+/// How often relative times ("2 minutes ago") are refreshed.
+const CLOCK_TICK_MS: u32 = 30_000;
+/// How long a download's Blob URL is kept after the click, so the browser has
+/// started reading it before it's revoked.
+const DOWNLOAD_REVOKE_MS: u32 = 10_000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BurnRecord {
+    /// Unix time in milliseconds.
+    pub timestamp: f64,
+    pub mint: String,
+    pub symbol: Option<String>,
+    pub amount: f64,
+    pub signature: String,
+    pub cluster: Cluster,
+    /// Memo sent with the burn, if any.
+    pub memo: Option<String>,
+}
+
+impl BurnRecord {
+    pub fn token_label(&self) -> String {
+        match &self.symbol {
+            Some(symbol) => symbol.clone(),
+            None => format!("{}…", self.mint.chars().take(4).collect::<String>()),
+        }
+    }
+
+    pub fn explorer_url(&self, explorer: Explorer) -> String {
+        explorer_url(&self.signature, self.cluster, explorer)
+    }
+
+    /// Plain-text summary of the burn suitable for pasting or tweeting.
+    pub fn receipt(&self, explorer: Explorer) -> String {
+        let timestamp: String = js_sys::Date::new(&self.timestamp.into()).to_iso_string().into();
+        format!(
+            "🔥 Burned {} {} on {}\n{}",
+            self.amount,
+            self.token_label(),
+            timestamp,
+            self.explorer_url(explorer)
+        )
+    }
+
+    /// Local calendar day of the burn as `YYYY-MM-DD`.
+    pub fn day(&self) -> String {
+        let date = js_sys::Date::new(&self.timestamp.into());
+        format!(
+            "{:04}-{:02}-{:02}",
+            date.get_full_year(),
+            date.get_month() + 1,
+            date.get_date()
+        )
+    }
+
+    /// Date and time of the burn in the user's locale and timezone.
+    pub fn local_time(&self) -> String {
+        js_sys::Date::new(&self.timestamp.into())
+            .to_locale_string("default", &JsValue::UNDEFINED)
+            .into()
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub records: Vec<BurnRecord>,
+}
+
+pub struct History {
+    now: f64,
+    _clock: Interval,
+}
+
+pub enum Msg {
+    DownloadCsv,
+    Tick,
+}
+
+impl Component for History {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        Self {
+            now: js_sys::Date::now(),
+            _clock: Interval::new(CLOCK_TICK_MS, move || link.send_message(Msg::Tick)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::DownloadCsv => {
+                if let Err(err) = download("burn-history.csv", &to_csv(&ctx.props().records)) {
+                    log::error!("CSV download failed: {:?}", err);
+                }
+                false
+            }
+            Msg::Tick => {
+                self.now = js_sys::Date::now();
+                !ctx.props().records.is_empty()
+            }
+        }
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.now = js_sys::Date::now();
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let records = &ctx.props().records;
+        let ondownload = ctx.link().callback(|_| Msg::DownloadCsv);
+
+        html! {
+            <div class="history">
+                <h2>{"Burn History"}</h2>
+                <button class="history-download" onclick={ondownload} disabled={records.is_empty()}>
+                    {"Download CSV"}
+                </button>
+                if records.is_empty() {
+                    <div class="empty-state">{"Nothing burned yet this session 🔥"}</div>
+                } else {
+                    {burn_chart(records)}
+                    <ul class="history-list">
+                        { for records.iter().rev().map(|record| html! {
+                            <li>
+                                {format!("{} {} — ", record.amount, record.token_label())}
+                                <AddressDisplay
+                                    address={record.signature.clone()}
+                                    kind={AddressKind::Signature}
+                                    cluster={record.cluster}
+                                />
+                                {" "}
+                                <time
+                                    class="history-time"
+                                    datetime={String::from(js_sys::Date::new(&record.timestamp.into()).to_iso_string())}
+                                    title={record.local_time()}
+                                >
+                                    {format_relative_time(record.timestamp, self.now)}
+                                </time>
+                                if let Some(memo) = &record.memo {
+                                    <div class="history-memo">{format!("“{}”", memo)}</div>
+                                }
+                            </li>
+                        }) }
+                    </ul>
+                }
+            </div>
+        }
+    }
+}
+
+/// Bar chart of burned amounts, one bar per (day, token).
+fn burn_chart(records: &[BurnRecord]) -> Html {
+    let mut totals: BTreeMap<(String, String), f64> = BTreeMap::new();
+    for record in records {
+        *totals.entry((record.day(), record.token_label())).or_default() += record.amount;
+    }
+
+    let max = totals.values().cloned().fold(0.0, f64::max);
+    let slot = CHART_WIDTH / totals.len() as f64;
+    let bar_width = slot * 0.7;
+
+    html! {
+        <svg
+            class="burn-chart"
+            viewBox={format!("0 0 {} {}", CHART_WIDTH, CHART_HEIGHT + LABEL_HEIGHT)}
+            role="img"
+            aria-label="Burned amounts by day and token"
+        >
+            { for totals.iter().enumerate().map(|(i, ((day, token), amount))| {
+                let height = if max > 0.0 { amount / max * CHART_HEIGHT } else { 0.0 };
+                let x = i as f64 * slot + (slot - bar_width) / 2.0;
+                html! {
+                    <g>
+                        <rect
+                            class="burn-chart-bar"
+                            x={x.to_string()}
+                            y={(CHART_HEIGHT - height).to_string()}
+                            width={bar_width.to_string()}
+                            height={height.to_string()}
+                        >
+                            <title>{format!("{} {}: {}", day, token, amount)}</title>
+                        </rect>
+                        <text
+                            class="burn-chart-label"
+                            x={(x + bar_width / 2.0).to_string()}
+                            y={(CHART_HEIGHT + 12.0).to_string()}
+                            text-anchor="middle"
+                        >
+                            {token.clone()}
+                        </text>
+                        <text
+                            class="burn-chart-label"
+                            x={(x + bar_width / 2.0).to_string()}
+                            y={(CHART_HEIGHT + 26.0).to_string()}
+                            text-anchor="middle"
+                        >
+                            {&day[5..]}
+                        </text>
+                    </g>
+                }
+            }) }
+        </svg>
+    }
+}
+
+const CSV_HEADER: &str = "timestamp,mint,symbol,amount,signature,memo";
+
+fn to_csv(records: &[BurnRecord]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for record in records {
+        let timestamp: String = js_sys::Date::new(&record.timestamp.into()).to_iso_string().into();
+        csv.push_str(&csv_row(record, &timestamp));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// One CSV line for `record`, without the line break. `timestamp` is passed
+/// in already formatted, so this stays pure.
+fn csv_row(record: &BurnRecord, timestamp: &str) -> String {
+    let amount = record.amount.to_string();
+    let fields = [
+        timestamp,
+        record.mint.as_str(),
+        record.symbol.as_deref().unwrap_or_default(),
+        amount.as_str(),
+        record.signature.as_str(),
+        record.memo.as_deref().unwrap_or_default(),
+    ];
+    fields.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(",")
+}
+
+/// Quotes `field` where CSV needs it. A field a spreadsheet would read as a
+/// formula (symbols come from token metadata anyone can set, memos are free
+/// text) gets a leading `'` so it's shown as text instead of evaluated.
+fn csv_field(field: &str) -> String {
+    let field = if field.starts_with(|c| matches!(c, '=' | '+' | '-' | '@' | '\t' | '\r')) {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+    if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+/// Saves `contents` as a file by clicking a temporary link to a Blob URL. The
+/// link is attached for the click, which Firefox needs, and the URL revoked
+/// later rather than straight away, which could cancel the download.
+fn download(filename: &str, contents: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let mut options = BlobPropertyBag::new();
+    options.type_("text/csv");
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let body = document.body().ok_or_else(|| JsValue::from_str("No document body"))?;
+    let anchor: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.set_hidden(true);
+    body.append_child(&anchor)?;
+    anchor.click();
+    anchor.remove();
+
+    Timeout::new(DOWNLOAD_REVOKE_MS, move || {
+        if let Err(err) = Url::revoke_object_url(&url) {
+            log::warn!("Could not revoke the download URL: {:?}", err);
+        }
+    })
+    .forget();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMESTAMP: &str = "2024-05-01T12:00:00.000Z";
+
+    fn record(symbol: Option<&str>, memo: Option<&str>) -> BurnRecord {
+        BurnRecord {
+            timestamp: 0.0,
+            mint: "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(),
+            symbol: symbol.map(str::to_string),
+            amount: 1.5,
+            signature: "5sig".to_string(),
+            cluster: Cluster::Mainnet,
+            memo: memo.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn plain_fields_are_left_unquoted() {
+        assert_eq!(
+            csv_row(&record(Some("USDC"), Some("gm")), TIMESTAMP),
+            "2024-05-01T12:00:00.000Z,EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v,USDC,1.5,5sig,gm"
+        );
+        assert_eq!(
+            csv_row(&record(None, None), TIMESTAMP),
+            "2024-05-01T12:00:00.000Z,EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v,,1.5,5sig,"
+        );
+    }
+
+    #[test]
+    fn commas_quotes_and_line_breaks_are_quoted() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("cr\rlf"), "\"cr\rlf\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn formula_like_fields_are_neutralized() {
+        assert_eq!(csv_field("=HYPERLINK(\"https://evil\")"), "\"'=HYPERLINK(\"\"https://evil\"\")\"");
+        assert_eq!(csv_field("+1"), "'+1");
+        assert_eq!(csv_field("-2+3"), "'-2+3");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("\tcmd"), "'\tcmd");
+        assert_eq!(csv_field("\rcmd"), "\"'\rcmd\"");
+        assert_eq!(csv_field("a=b"), "a=b");
+        let row = csv_row(&record(Some("=cmd"), Some("@me")), TIMESTAMP);
+        assert!(row.ends_with(",'=cmd,1.5,5sig,'@me"));
+    }
+
+    #[test]
+    fn a_quoted_memo_stays_one_field() {
+        let row = csv_row(&record(Some("A,B"), Some("burn \"it\",\nall")), TIMESTAMP);
+        assert!(row.ends_with(",\"A,B\",1.5,5sig,\"burn \"\"it\"\",\nall\""));
+    }
+}
@@ -1,2 +1,13 @@
 pub mod wallet;
 pub mod burn_form;
+pub mod history;
+pub mod error_boundary;
+pub mod settings;
+pub mod dust_scanner;
+pub mod modal;
+pub mod confirm_challenge;
+pub mod address_display;
+pub mod tooltip;
+pub mod banner;
+pub mod onboarding;
+pub mod token_logo;
@@ -0,0 +1,137 @@
+use yew::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
+
+/// What Tab can land on inside the dialog.
+const FOCUSABLE: &str = "button:not([disabled]), [href], input:not([disabled]), select:not([disabled]), \
+                         textarea:not([disabled]), [tabindex]:not([tabindex=\"-1\"])";
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub title: AttrValue,
+    pub on_cancel: Callback<()>,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Dialog over a dimmed backdrop. Clicking the backdrop or pressing Escape
+/// cancels. Focus moves into the dialog when it opens, Tab cycles within it,
+/// and focus goes back to whatever opened it once it closes.
+pub struct Modal {
+    dialog: NodeRef,
+    /// Focused when the dialog opened; usually the button that opened it.
+    trigger: Option<HtmlElement>,
+}
+
+impl Component for Modal {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        let trigger = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.active_element())
+            .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+        Self { dialog: NodeRef::default(), trigger }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, first_render: bool) {
+        if !first_render {
+            return;
+        }
+        let target = focusables(&self.dialog)
+            .into_iter()
+            .next()
+            .or_else(|| self.dialog.cast::<HtmlElement>());
+        if let Some(target) = target {
+            let _ = target.focus();
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(trigger) = self.trigger.take().filter(|trigger| trigger.is_connected()) {
+            let _ = trigger.focus();
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let onbackdrop = props.on_cancel.reform(|_: MouseEvent| ());
+        let onkeydown = {
+            let on_cancel = props.on_cancel.clone();
+            let dialog = self.dialog.clone();
+            Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+                "Escape" => on_cancel.emit(()),
+                "Tab" => trap_focus(&dialog, &e),
+                _ => {}
+            })
+        };
+
+        html! {
+            <div class="modal-backdrop" onclick={onbackdrop}>
+                <div
+                    class="modal"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-label={props.title.clone()}
+                    tabindex="-1"
+                    ref={self.dialog.clone()}
+                    onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}
+                    {onkeydown}
+                >
+                    <h2>{props.title.clone()}</h2>
+                    { for props.children.iter() }
+                </div>
+            </div>
+        }
+    }
+}
+
+/// Enabled controls inside the dialog, in tab order.
+fn focusables(dialog: &NodeRef) -> Vec<HtmlElement> {
+    let nodes = match dialog.cast::<Element>().and_then(|dialog| dialog.query_selector_all(FOCUSABLE).ok()) {
+        Some(nodes) => nodes,
+        None => return Vec::new(),
+    };
+    (0..nodes.length())
+        .filter_map(|i| nodes.item(i))
+        .filter_map(|node| node.dyn_into::<HtmlElement>().ok())
+        .collect()
+}
+
+/// Wraps Tab from the last control to the first, and Shift+Tab from the
+/// first to the last, so focus can't leave the dialog while it's open.
+fn trap_focus(dialog: &NodeRef, e: &KeyboardEvent) {
+    let controls = focusables(dialog);
+    let (first, last) = match (controls.first(), controls.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => {
+            e.prevent_default();
+            return;
+        }
+    };
+    let active = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.active_element());
+    let is_active = |control: &HtmlElement| {
+        active.as_ref().map_or(false, |active| active.is_same_node(Some(control.as_ref())))
+    };
+    let inside = match (&active, dialog.cast::<Element>()) {
+        (Some(active), Some(dialog)) => dialog.contains(Some(active.as_ref())) && !dialog.is_same_node(Some(active.as_ref())),
+        _ => false,
+    };
+
+    let next = if !inside {
+        Some(if e.shift_key() { last } else { first })
+    } else if e.shift_key() && is_active(first) {
+        Some(last)
+    } else if !e.shift_key() && is_active(last) {
+        Some(first)
+    } else {
+        None
+    };
+    if let Some(next) = next {
+        e.prevent_default();
+        let _ = next.focus();
+    }
+}
@@ -0,0 +1,115 @@
+use yew::prelude::*;
+use crate::context::WalletContext;
+use crate::storage;
+
+/// Set once the walkthrough has been finished or skipped.
+const DONE_KEY: &str = "onboarding_done";
+
+/// (title, tip) for each step, in the order the burn flow goes.
+const STEPS: [(&str, &str); 4] = [
+    (
+        "Connect your wallet",
+        "Click Connect Phantom. The burner never sees your keys: every burn is approved in your wallet.",
+    ),
+    (
+        "Pick a token",
+        "Paste the token's mint address. Its decimals are looked up and your balance is checked for you.",
+    ),
+    (
+        "Enter an amount",
+        "Type an amount or use Max. The bar under the field shows how much of your balance you're burning.",
+    ),
+    (
+        "Confirm",
+        "Review the summary and confirm, then approve in your wallet. Burned tokens are gone for good.",
+    ),
+];
+
+/// First-visit walkthrough of the burn flow, shown as a card over the page.
+/// It moves past the connect step by itself once a wallet connects.
+pub struct Onboarding {
+    step: Option<usize>,
+    _wallet_handle: Option<ContextHandle<WalletContext>>,
+}
+
+pub enum Msg {
+    Next,
+    Back,
+    Finish,
+    WalletChanged(WalletContext),
+}
+
+impl Component for Onboarding {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (wallet, wallet_handle) = match ctx
+            .link()
+            .context::<WalletContext>(ctx.link().callback(Msg::WalletChanged))
+        {
+            Some((wallet, handle)) => (wallet, Some(handle)),
+            None => (WalletContext::default(), None),
+        };
+        let done = storage::get::<bool>(DONE_KEY).ok().flatten().unwrap_or(false);
+
+        Self {
+            step: (!done).then_some(if wallet.connected { 1 } else { 0 }),
+            _wallet_handle: wallet_handle,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let step = match self.step {
+            Some(step) => step,
+            None => return false,
+        };
+        match msg {
+            Msg::Next if step + 1 < STEPS.len() => self.step = Some(step + 1),
+            Msg::Next | Msg::Finish => {
+                let _ = storage::set(DONE_KEY, &true);
+                self.step = None;
+            }
+            Msg::Back => self.step = Some(step.saturating_sub(1)),
+            Msg::WalletChanged(wallet) => {
+                if !(wallet.connected && step == 0) {
+                    return false;
+                }
+                self.step = Some(1);
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let step = match self.step {
+            Some(step) => step,
+            None => return html! {},
+        };
+        let (title, tip) = STEPS[step];
+        let last = step + 1 == STEPS.len();
+
+        html! {
+            <div class="onboarding" role="dialog" aria-label="Getting started">
+                <div class="onboarding-progress">
+                    {format!("Step {} of {}", step + 1, STEPS.len())}
+                </div>
+                <h3>{title}</h3>
+                <p>{tip}</p>
+                <div class="onboarding-actions">
+                    <button type="button" class="secondary-button" onclick={ctx.link().callback(|_| Msg::Finish)}>
+                        {"Skip"}
+                    </button>
+                    if step > 0 {
+                        <button type="button" class="secondary-button" onclick={ctx.link().callback(|_| Msg::Back)}>
+                            {"Back"}
+                        </button>
+                    }
+                    <button type="button" onclick={ctx.link().callback(|_| Msg::Next)}>
+                        {if last { "Got it" } else { "Next" }}
+                    </button>
+                </div>
+            </div>
+        }
+    }
+}
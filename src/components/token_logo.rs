@@ -0,0 +1,73 @@
+use yew::prelude::*;
+use crate::utils::https_url;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    /// Logo URL from the token's metadata; only `https://` URLs are loaded.
+    #[prop_or_default]
+    pub src: Option<AttrValue>,
+    /// Shown as the image's alt text and, initialled, as the placeholder.
+    pub symbol: AttrValue,
+}
+
+/// A token's logo, so the user can see at a glance it's the asset they meant.
+/// Loads lazily and falls back to a lettered placeholder when there's no
+/// usable URL or the image fails to load.
+pub struct TokenLogo {
+    failed: bool,
+}
+
+pub enum Msg {
+    Failed,
+}
+
+impl Component for TokenLogo {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { failed: false }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Failed => {
+                self.failed = true;
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        // A new URL deserves its own try.
+        if ctx.props().src != old_props.src {
+            self.failed = false;
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let src = props.src.as_deref().and_then(https_url).filter(|_| !self.failed);
+
+        match src {
+            Some(src) => html! {
+                <img
+                    class="token-logo"
+                    {src}
+                    alt={format!("{} logo", props.symbol)}
+                    loading="lazy"
+                    referrerpolicy="no-referrer"
+                    width="32"
+                    height="32"
+                    onerror={ctx.link().callback(|_| Msg::Failed)}
+                />
+            },
+            None => html! {
+                <span class="token-logo placeholder" aria-hidden="true">
+                    {props.symbol.chars().next().map_or('?', |c| c.to_ascii_uppercase())}
+                </span>
+            },
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    /// Accessible name of the trigger button.
+    pub label: AttrValue,
+    /// Ties the trigger to the popover for screen readers; unique per page.
+    pub id: AttrValue,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// An info button that toggles a small popover. It opens on click, Enter or
+/// Space and closes on a second press or Escape.
+pub struct Tooltip {
+    open: bool,
+}
+
+pub enum Msg {
+    Toggle,
+    Close,
+}
+
+impl Component for Tooltip {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { open: false }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Toggle => self.open = !self.open,
+            Msg::Close => {
+                if !self.open {
+                    return false;
+                }
+                self.open = false;
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let ontoggle = ctx.link().callback(|_: MouseEvent| Msg::Toggle);
+        let onkeydown = ctx.link().batch_callback(|e: KeyboardEvent| {
+            (e.key() == "Escape").then_some(Msg::Close)
+        });
+
+        html! {
+            <span class="tooltip" {onkeydown}>
+                <button
+                    type="button"
+                    class="tooltip-trigger"
+                    aria-label={props.label.clone()}
+                    aria-expanded={self.open.to_string()}
+                    aria-controls={props.id.clone()}
+                    onclick={ontoggle}
+                >
+                    {"ⓘ"}
+                </button>
+                if self.open {
+                    <span class="tooltip-popover" role="tooltip" id={props.id.clone()}>
+                        { for props.children.iter() }
+                    </span>
+                }
+            </span>
+        }
+    }
+}
@@ -1,8 +1,12 @@
+use std::cell::Cell;
+use std::rc::Rc;
 use yew::prelude::*;
 use wasm_bindgen::prelude::*;
-use web_sys::Window;
-use js_sys::Object;
+use js_sys::{Object, Reflect};
 use wasm_bindgen::JsCast;
+use crate::components::address_display::{AddressDisplay, AddressKind};
+use crate::interop;
+use crate::utils::{is_valid_signature, Cluster};
 
 #[wasm_bindgen]
 extern "C" {
@@ -15,19 +19,32 @@ extern "C" {
 
 pub struct WalletConnect {
     connected: bool,
+    connecting: bool,
     pub_key: Option<String>,
-    on_connect: Callback<bool>,
+    error: Option<String>,
+    on_connect: Callback<(bool, Option<String>)>,
+    /// Provider event handlers by event name, kept alive until they're
+    /// unregistered.
+    listeners: Vec<(&'static str, Closure<dyn FnMut(JsValue)>)>,
+    /// Cancel flag of the pending connect; set when it's superseded or the
+    /// component goes away so the task's answer is dropped.
+    pending_connect: Option<Rc<Cell<bool>>>,
 }
 
 pub enum Msg {
     Connect,
+    Switch,
     Connected(bool, Option<String>),
+    AccountChanged(String),
+    Disconnected,
     Error(String),
 }
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
-    pub on_connect: Callback<bool>,
+    pub on_connect: Callback<(bool, Option<String>)>,
+    #[prop_or_default]
+    pub cluster: Cluster,
 }
 
 impl Component for WalletConnect {
@@ -35,78 +52,424 @@ impl Component for WalletConnect {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        Self {
-            connected: false,
-            pub_key: None,
-            on_connect: ctx.props().on_connect.clone(),
-        }
+        Self::new(ctx.props().on_connect.clone())
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Connect => {
-                let link = ctx.link().clone();
+                if !self.begin_connect() {
+                    return false;
+                }
+                let task = self.start_connect(ctx);
                 wasm_bindgen_futures::spawn_local(async move {
-                    let window = web_sys::window().unwrap();
-                    if let Some(phantom) = js_sys::Reflect::get(
-                        &window,
-                        &JsValue::from_str("solana")
-                    ).ok() {
-                        if let Ok(connect_result) = js_sys::Reflect::get(
-                            &phantom,
-                            &JsValue::from_str("connect")
-                        ) {
-                            if let Some(func) = connect_result.dyn_ref::<js_sys::Function>() {
-                                if let Ok(_) = func.call0(&phantom) {
-                                    let pub_key = js_sys::Reflect::get(
-                                        &phantom,
-                                        &JsValue::from_str("publicKey")
-                                    ).ok()
-                                    .and_then(|key| key.as_string());
-
-                                    link.send_message(Msg::Connected(true, pub_key));
-                                }
-                            }
-                        }
-                    } else {
-                        link.send_message(Msg::Error("Phantom wallet not found".to_string()));
+                    task.send(match connect().await {
+                        Ok(pub_key) => Msg::Connected(true, pub_key),
+                        Err(error) => Msg::Error(error),
+                    });
+                });
+                true
+            }
+            Msg::Switch => {
+                if !self.begin_switch() {
+                    return false;
+                }
+                let task = self.start_connect(ctx);
+                wasm_bindgen_futures::spawn_local(async move {
+                    if let Err(error) = disconnect().await {
+                        log(&format!("Disconnect before switching failed: {}", error));
                     }
+                    task.send(match connect().await {
+                        Ok(pub_key) => Msg::Connected(true, pub_key),
+                        Err(error) => Msg::Error(error),
+                    });
                 });
-                false
+                true
             }
             Msg::Connected(status, key) => {
-                self.connected = status;
-                self.pub_key = key;
-                self.on_connect.emit(status);
+                self.connected(status, key);
+                if status {
+                    self.subscribe(ctx);
+                }
                 true
             }
-            Msg::Error(_) => {
-                self.connected = false;
+            Msg::AccountChanged(key) => self.account_changed(key),
+            Msg::Disconnected => self.disconnected(),
+            Msg::Error(error) => {
+                self.failed(error);
                 true
             }
         }
     }
 
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.cancel_connect();
+        self.unsubscribe();
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let onclick = ctx.link().callback(|_| Msg::Connect);
+        let onswitch = ctx.link().callback(|_| Msg::Switch);
 
         html! {
             <div class="wallet-section">
                 if !self.connected {
-                    <button class="connect-button" {onclick}>
-                        {"Connect Phantom"}
+                    <button class="connect-button" {onclick} disabled={self.connecting}>
+                        if self.connecting {
+                            {"Connecting..."}
+                        } else {
+                            {"Connect Phantom"}
+                        }
                     </button>
+                    if let Some(error) = &self.error {
+                        <div class="error">{error}</div>
+                    }
                 } else {
                     <div class="connected-status">
                         {"Wallet Connected"}
                         if let Some(key) = &self.pub_key {
                             <div class="wallet-address">
-                                {format!("Address: {}...{}", &key[..6], &key[key.len()-6..])}
+                                {"Address: "}
+                                <AddressDisplay
+                                    address={key.clone()}
+                                    kind={AddressKind::Wallet}
+                                    cluster={ctx.props().cluster}
+                                />
                             </div>
                         }
+                        <button type="button" class="secondary-button" onclick={onswitch}>
+                            {"Switch wallet"}
+                        </button>
                     </div>
                 }
             </div>
         }
     }
-}
\ No newline at end of file
+}
+
+/// Scope handle for a spawned connect that drops its answer once cancelled.
+struct ConnectTask {
+    link: yew::html::Scope<WalletConnect>,
+    cancelled: Rc<Cell<bool>>,
+}
+
+impl ConnectTask {
+    fn send(&self, msg: Msg) {
+        if !self.cancelled.get() {
+            self.link.send_message(msg);
+        }
+    }
+}
+
+impl WalletConnect {
+    fn new(on_connect: Callback<(bool, Option<String>)>) -> Self {
+        Self {
+            connected: false,
+            connecting: false,
+            pub_key: None,
+            error: None,
+            on_connect,
+            listeners: Vec::new(),
+            pending_connect: None,
+        }
+    }
+
+    /// Marks a connect as started; `false` while one is already underway.
+    fn begin_connect(&mut self) -> bool {
+        if self.connecting {
+            return false;
+        }
+        self.connecting = true;
+        self.error = None;
+        true
+    }
+
+    /// Like [`Self::begin_connect`], but drops the old account right away: if
+    /// the new prompt is cancelled the user stays disconnected rather than
+    /// quietly keeping the wallet they meant to leave.
+    fn begin_switch(&mut self) -> bool {
+        if !self.begin_connect() {
+            return false;
+        }
+        self.connected = false;
+        self.pub_key = None;
+        self.on_connect.emit((false, None));
+        true
+    }
+
+    /// A connect finished, with `status` saying whether it got an account.
+    fn connected(&mut self, status: bool, key: Option<String>) {
+        self.connecting = false;
+        self.pending_connect = None;
+        self.connected = status;
+        self.pub_key = key.clone();
+        self.on_connect.emit((status, key));
+    }
+
+    /// The user picked another account in the extension.
+    fn account_changed(&mut self, key: String) -> bool {
+        if self.connecting || !self.connected || self.pub_key.as_deref() == Some(key.as_str()) {
+            return false;
+        }
+        self.pub_key = Some(key.clone());
+        self.on_connect.emit((true, Some(key)));
+        true
+    }
+
+    /// The provider reported the wallet gone.
+    fn disconnected(&mut self) -> bool {
+        // Switching disconnects on purpose; the reconnect reports back.
+        if self.connecting {
+            return false;
+        }
+        self.connected = false;
+        self.pub_key = None;
+        self.on_connect.emit((false, None));
+        true
+    }
+
+    fn failed(&mut self, error: String) {
+        self.connecting = false;
+        self.pending_connect = None;
+        self.connected = false;
+        self.error = Some(error);
+    }
+
+    /// Cancels any connect still pending and hands out a task for a new one.
+    fn start_connect(&mut self, ctx: &Context<Self>) -> ConnectTask {
+        self.cancel_connect();
+        let cancelled = Rc::new(Cell::new(false));
+        self.pending_connect = Some(cancelled.clone());
+        ConnectTask { link: ctx.link().clone(), cancelled }
+    }
+
+    fn cancel_connect(&mut self) {
+        if let Some(cancelled) = self.pending_connect.take() {
+            cancelled.set(true);
+        }
+    }
+
+    /// Listens for the provider reporting that the wallet went away, either by
+    /// disconnecting or by switching to no account (extension locked), or
+    /// that the user picked another account in the extension.
+    /// Replaces any earlier handlers, so reconnecting doesn't stack them.
+    fn subscribe(&mut self, ctx: &Context<Self>) {
+        self.unsubscribe();
+        let (phantom, on) = match interop::provider()
+            .and_then(|phantom| interop::function(&phantom, "on").map(|on| (phantom, on)))
+        {
+            Ok(found) => found,
+            Err(error) => {
+                log(&format!("Wallet events unavailable: {}", error));
+                return;
+            }
+        };
+
+        let link = ctx.link().clone();
+        let on_disconnect = Closure::wrap(Box::new(move |_: JsValue| {
+            link.send_message(Msg::Disconnected);
+        }) as Box<dyn FnMut(JsValue)>);
+
+        let link = ctx.link().clone();
+        let on_account_changed = Closure::wrap(Box::new(move |key: JsValue| {
+            match key_to_string(&key) {
+                Some(key) => link.send_message(Msg::AccountChanged(key)),
+                None => link.send_message(Msg::Disconnected),
+            }
+        }) as Box<dyn FnMut(JsValue)>);
+
+        for (event, handler) in [("disconnect", on_disconnect), ("accountChanged", on_account_changed)] {
+            if let Err(err) = on.call2(&phantom, &JsValue::from_str(event), handler.as_ref()) {
+                log(&format!("Could not subscribe to {}: {}", event, interop::describe(&err)));
+                continue;
+            }
+            self.listeners.push((event, handler));
+        }
+    }
+
+    /// Takes the handlers off the provider before they're dropped; a dropped
+    /// closure still registered would throw the next time the event fires.
+    fn unsubscribe(&mut self) {
+        if self.listeners.is_empty() {
+            return;
+        }
+        let listeners = std::mem::take(&mut self.listeners);
+        let (phantom, off) = match interop::provider()
+            .and_then(|phantom| interop::function(&phantom, "off").map(|off| (phantom, off)))
+        {
+            Ok(found) => found,
+            Err(error) => {
+                // Nowhere to unregister them, so keep them alive instead.
+                log(&format!("Could not unsubscribe from wallet events: {}", error));
+                std::mem::forget(listeners);
+                return;
+            }
+        };
+        for (event, handler) in listeners {
+            if let Err(err) = off.call2(&phantom, &JsValue::from_str(event), handler.as_ref()) {
+                log(&format!("Could not unsubscribe from {}: {}", event, interop::describe(&err)));
+                handler.forget();
+            }
+        }
+    }
+}
+
+/// Asks Phantom to connect and reads back the connected address. Every step
+/// that can go wrong reports why, so the button never just does nothing.
+async fn connect() -> Result<Option<String>, String> {
+    let phantom = interop::wait_for_provider().await?;
+    let pending = interop::function(&phantom, "connect")?
+        .call0(&phantom)
+        .map_err(|err| interop::describe(&err))?;
+    wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&pending))
+        .await
+        .map_err(|_| "Connection rejected".to_string())?;
+
+    let key = interop::get(&phantom, "publicKey")?;
+    match key_to_string(&key) {
+        Some(key) => Ok(Some(key)),
+        None => Err("Wallet connected without an address".to_string()),
+    }
+}
+
+/// Asks Phantom to disconnect the current account.
+async fn disconnect() -> Result<(), String> {
+    let phantom = interop::provider()?;
+    let pending = interop::function(&phantom, "disconnect")?
+        .call0(&phantom)
+        .map_err(|err| interop::describe(&err))?;
+    wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&pending))
+        .await
+        .map(|_| ())
+        .map_err(|err| interop::describe(&err))
+}
+
+/// Phantom exposes `publicKey` as a `PublicKey` object whose `toString()` is
+/// the base58 address.
+fn key_to_string(key: &JsValue) -> Option<String> {
+    if key.is_null() || key.is_undefined() {
+        return None;
+    }
+    key.as_string()
+        .or_else(|| key.dyn_ref::<Object>().map(|key| String::from(key.to_string())))
+}
+
+/// Has the wallet sign and submit a base58-encoded message, returning the
+/// transaction signature.
+pub async fn sign_and_send(message: &str) -> Result<String, String> {
+    let phantom = interop::provider()?;
+    let request = interop::function(&phantom, "request")?;
+
+    let params = Object::new();
+    let args = Object::new();
+    Reflect::set(&params, &JsValue::from_str("message"), &JsValue::from_str(message))
+        .and_then(|_| Reflect::set(&args, &JsValue::from_str("method"), &JsValue::from_str("signAndSendTransaction")))
+        .and_then(|_| Reflect::set(&args, &JsValue::from_str("params"), &params))
+        .map_err(|err| interop::describe(&err))?;
+
+    let pending = request
+        .call1(&phantom, &args)
+        .map_err(|err| interop::describe(&err))?;
+    let result = wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&pending))
+        .await
+        .map_err(|err| interop::describe(&err))?;
+    let signature = interop::get(&result, "signature")?
+        .as_string()
+        .ok_or_else(|| "Wallet returned no signature".to_string())?;
+    if !is_valid_signature(&signature) {
+        return Err("Wallet returned a malformed signature".to_string());
+    }
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    const KEY: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    type Reports = Rc<RefCell<Vec<(bool, Option<String>)>>>;
+
+    /// A wallet button whose `on_connect` reports are collected.
+    fn wallet() -> (WalletConnect, Reports) {
+        let reports = Reports::default();
+        let sink = reports.clone();
+        (WalletConnect::new(Callback::from(move |report| sink.borrow_mut().push(report))), reports)
+    }
+
+    fn connected_wallet() -> (WalletConnect, Reports) {
+        let (mut wallet, reports) = wallet();
+        wallet.begin_connect();
+        wallet.connected(true, Some(KEY.to_string()));
+        reports.borrow_mut().clear();
+        (wallet, reports)
+    }
+
+    #[test]
+    fn disconnect_clears_the_account_and_reports_it() {
+        let (mut wallet, reports) = connected_wallet();
+        assert!(wallet.disconnected());
+        assert!(!wallet.connected);
+        assert_eq!(wallet.pub_key, None);
+        assert_eq!(*reports.borrow(), vec![(false, None)]);
+    }
+
+    #[test]
+    fn disconnect_during_a_switch_is_left_to_the_reconnect() {
+        let (mut wallet, reports) = connected_wallet();
+        assert!(wallet.begin_switch());
+        assert!(!wallet.disconnected());
+        // Only the switch itself reported the old account gone.
+        assert_eq!(*reports.borrow(), vec![(false, None)]);
+    }
+
+
+    #[test]
+    fn connecting_is_set_until_the_wallet_answers() {
+        let (mut wallet, reports) = wallet();
+        assert!(wallet.begin_connect());
+        assert!(wallet.connecting);
+        // A second click while the prompt is open does nothing.
+        assert!(!wallet.begin_connect());
+
+        wallet.connected(true, Some(KEY.to_string()));
+        assert!(!wallet.connecting);
+        assert!(wallet.connected);
+        assert_eq!(*reports.borrow(), vec![(true, Some(KEY.to_string()))]);
+        assert!(wallet.begin_connect());
+    }
+
+    #[test]
+    fn a_failed_connect_clears_connecting_and_shows_why() {
+        let (mut wallet, reports) = wallet();
+        wallet.begin_connect();
+        wallet.failed("Connection rejected".to_string());
+        assert!(!wallet.connecting);
+        assert!(!wallet.connected);
+        assert_eq!(wallet.error.as_deref(), Some("Connection rejected"));
+        assert!(reports.borrow().is_empty());
+
+        // Trying again hides the old error.
+        assert!(wallet.begin_connect());
+        assert_eq!(wallet.error, None);
+    }
+
+    #[test]
+    fn switching_is_a_connect_that_drops_the_old_account() {
+        let (mut wallet, reports) = connected_wallet();
+        assert!(wallet.begin_switch());
+        assert!(wallet.connecting && !wallet.connected);
+        assert_eq!(wallet.pub_key, None);
+        assert!(!wallet.begin_switch());
+        assert!(!wallet.begin_connect());
+        assert_eq!(*reports.borrow(), vec![(false, None)]);
+    }
+
+    #[test]
+    fn account_changes_are_ignored_while_connecting() {
+        let (mut wallet, _) = connected_wallet();
+        wallet.begin_switch();
+        assert!(!wallet.account_changed("Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr".to_string()));
+    }
+}
@@ -0,0 +1,216 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::transaction::{CONFIRM_MAX_ATTEMPTS, CONFIRM_POLL_INTERVAL_MS};
+use crate::utils::{Cluster, Explorer, RoundingMode};
+
+/// Mint decimals fetched during this session, shared with components through a
+/// `ContextProvider`. Entries only hold for the cluster they were fetched on.
+#[derive(Clone, Default)]
+pub struct DecimalsCache {
+    cluster: Cluster,
+    entries: Rc<RefCell<HashMap<String, u8>>>,
+}
+
+impl PartialEq for DecimalsCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.cluster == other.cluster && Rc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+impl DecimalsCache {
+    pub fn cluster(&self) -> Cluster {
+        self.cluster
+    }
+
+    pub fn get(&self, mint: &str) -> Option<u8> {
+        self.entries.borrow().get(mint).copied()
+    }
+
+    pub fn insert(&self, mint: String, decimals: u8) {
+        self.entries.borrow_mut().insert(mint, decimals);
+    }
+
+    /// Returns the cache to use on `cluster`, dropping every entry if it differs
+    /// from the current one.
+    pub fn for_cluster(&self, cluster: Cluster) -> Self {
+        if cluster == self.cluster {
+            return self.clone();
+        }
+        Self {
+            cluster,
+            entries: Rc::default(),
+        }
+    }
+}
+
+/// Mints with a burn being signed or awaiting confirmation, shared so a second
+/// burn of the same token is caught while the first is unresolved. Counted,
+/// as the form and the dust scanner can overlap on a mint.
+#[derive(Clone, Default)]
+pub struct InFlightBurns {
+    mints: Rc<RefCell<HashMap<String, usize>>>,
+}
+
+impl PartialEq for InFlightBurns {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.mints, &other.mints)
+    }
+}
+
+impl InFlightBurns {
+    pub fn contains(&self, mint: &str) -> bool {
+        self.mints.borrow().contains_key(mint)
+    }
+
+    pub fn start(&self, mint: &str) {
+        *self.mints.borrow_mut().entry(mint.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn finish(&self, mint: &str) {
+        let mut mints = self.mints.borrow_mut();
+        if let Some(count) = mints.get_mut(mint) {
+            *count -= 1;
+            if *count == 0 {
+                mints.remove(mint);
+            }
+        }
+    }
+}
+
+/// Connection state of the user's wallet as reported by `WalletConnect`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WalletContext {
+    pub connected: bool,
+    pub public_key: Option<String>,
+}
+
+/// User preferences edited in the settings panel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub rounding: RoundingMode,
+    /// Amount the touch stepper adds or removes per tap, in UI units.
+    pub stepper_step: f64,
+    /// Share of the balance, in percent, above which a burn has to be
+    /// confirmed by typing a phrase.
+    pub confirm_challenge_percent: f64,
+    /// Share of the mint's total supply, in percent, at or above which a burn
+    /// gets a prominent warning and the typed confirmation.
+    pub full_supply_warning_percent: f64,
+    /// Opt-in anonymous usage counts; off unless the user enables it.
+    pub telemetry: bool,
+    /// Seconds of unconfirmed polling after which a burn is reported as
+    /// taking longer than usual (it keeps being polled).
+    pub slow_confirm_seconds: u32,
+    /// Milliseconds between confirmation polls.
+    pub confirm_poll_interval_ms: u32,
+    /// Polls before a burn is reported as unconfirmed.
+    pub confirm_max_attempts: u32,
+    /// RPC endpoint used instead of the public ones; empty for the defaults.
+    pub custom_rpc_url: String,
+    /// Simulate burns instead of sending them; for trying the flow safely.
+    pub dry_run: bool,
+    /// Seconds before a success message clears itself; 0 keeps it.
+    pub success_dismiss_seconds: u32,
+    /// Seconds before an error message clears itself; 0 keeps it.
+    pub error_dismiss_seconds: u32,
+    /// Where signature and address links point.
+    pub explorer: Explorer,
+    /// Denser layout without the decorative extras, for burning many tokens.
+    pub compact: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            rounding: RoundingMode::default(),
+            stepper_step: 1.0,
+            confirm_challenge_percent: 50.0,
+            full_supply_warning_percent: 90.0,
+            telemetry: false,
+            slow_confirm_seconds: 20,
+            confirm_poll_interval_ms: CONFIRM_POLL_INTERVAL_MS,
+            confirm_max_attempts: CONFIRM_MAX_ATTEMPTS,
+            custom_rpc_url: String::new(),
+            dry_run: false,
+            success_dismiss_seconds: 10,
+            error_dismiss_seconds: 0,
+            explorer: Explorer::default(),
+            compact: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    #[test]
+    fn decimals_cache_returns_inserted_entries() {
+        let cache = DecimalsCache::default();
+        assert_eq!(cache.get(MINT), None);
+        cache.insert(MINT.to_string(), 6);
+        assert_eq!(cache.get(MINT), Some(6));
+    }
+
+    #[test]
+    fn decimals_cache_keeps_entries_on_the_same_cluster() {
+        let cache = DecimalsCache::default();
+        cache.insert(MINT.to_string(), 6);
+        let same = cache.for_cluster(cache.cluster());
+        assert_eq!(same.get(MINT), Some(6));
+        assert!(same == cache);
+    }
+
+    #[test]
+    fn decimals_cache_drops_entries_on_another_cluster() {
+        let cache = DecimalsCache::default();
+        cache.insert(MINT.to_string(), 6);
+        let other = cache.for_cluster(Cluster::Devnet);
+        assert_eq!(other.cluster(), Cluster::Devnet);
+        assert_eq!(other.get(MINT), None);
+        assert!(other != cache);
+        // The original cache is left as it was.
+        assert_eq!(cache.get(MINT), Some(6));
+    }
+
+    #[test]
+    fn decimals_caches_compare_by_shared_entries() {
+        let cache = DecimalsCache::default();
+        assert!(cache.clone() == cache);
+        assert!(DecimalsCache::default() != cache);
+    }
+
+    #[test]
+    fn in_flight_burns_are_counted() {
+        let burns = InFlightBurns::default();
+        assert!(!burns.contains(MINT));
+        burns.start(MINT);
+        burns.start(MINT);
+        burns.finish(MINT);
+        assert!(burns.contains(MINT));
+        burns.finish(MINT);
+        assert!(!burns.contains(MINT));
+    }
+
+    #[test]
+    fn finishing_an_unknown_mint_is_a_no_op() {
+        let burns = InFlightBurns::default();
+        burns.finish(MINT);
+        assert!(!burns.contains(MINT));
+        burns.start(MINT);
+        burns.finish("other");
+        assert!(burns.contains(MINT));
+    }
+
+    #[test]
+    fn in_flight_burns_are_shared_between_clones() {
+        let burns = InFlightBurns::default();
+        let clone = burns.clone();
+        clone.start(MINT);
+        assert!(burns.contains(MINT));
+        assert!(clone == burns);
+    }
+}
@@ -0,0 +1,207 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use js_sys::{Function, Reflect};
+use crate::utils::{Cluster, NumberSeparators};
+
+/// Reads `target[key]`, treating a missing (`undefined`) property as an error
+/// rather than a value, so callers can't silently carry on with nothing.
+pub fn get(target: &JsValue, key: &str) -> Result<JsValue, String> {
+    match Reflect::get(target, &JsValue::from_str(key)) {
+        Ok(value) if value.is_undefined() => Err(format!("`{}` is not available", key)),
+        Ok(value) => Ok(value),
+        Err(err) => Err(describe(&err)),
+    }
+}
+
+/// Reads `target[key]` and checks that it is callable.
+pub fn function(target: &JsValue, key: &str) -> Result<Function, String> {
+    get(target, key)?
+        .dyn_into::<Function>()
+        .map_err(|_| format!("`{}` is not a function", key))
+}
+
+/// The injected Phantom provider, `window.solana`.
+pub fn provider() -> Result<JsValue, String> {
+    let window = web_sys::window().ok_or_else(|| "No browser window".to_string())?;
+    match get(&window, "solana") {
+        Ok(phantom) if !phantom.is_null() => Ok(phantom),
+        _ => Err("Phantom wallet not found".to_string()),
+    }
+}
+
+/// Gap between checks for a provider that isn't injected yet.
+const PROVIDER_POLL_MS: u32 = 200;
+/// Checks before concluding there is no provider; about two seconds.
+const PROVIDER_POLL_ATTEMPTS: u32 = 10;
+
+/// Like [`provider`], but on a fast page load the extension may not have
+/// injected `window.solana` yet, so it keeps checking for a little while
+/// before giving up.
+pub async fn wait_for_provider() -> Result<JsValue, String> {
+    for _ in 0..PROVIDER_POLL_ATTEMPTS {
+        if let Ok(phantom) = provider() {
+            return Ok(phantom);
+        }
+        sleep(PROVIDER_POLL_MS).await;
+    }
+    provider()
+}
+
+/// Whether the connected account is on a hardware wallet. Phantom doesn't
+/// document this, so it's best effort: a provider flagging `isLedger` (or the
+/// more generic `isHardware`) counts, anything else is treated as a software
+/// wallet.
+pub fn is_hardware_wallet() -> bool {
+    provider().map_or(false, |phantom| {
+        ["isLedger", "isHardware"]
+            .iter()
+            .any(|flag| get(&phantom, flag).map_or(false, |value| value.as_bool() == Some(true)))
+    })
+}
+
+/// Number separators for the browser's language (`navigator.language`), or
+/// the dot-decimal default when it can't be read.
+pub fn number_separators() -> NumberSeparators {
+    web_sys::window()
+        .and_then(|window| Reflect::get(&window.navigator(), &JsValue::from_str("language")).ok())
+        .and_then(|language| language.as_string())
+        .map(|language| {
+            let sample = js_sys::Number::from(12345.6).to_locale_string(&language);
+            NumberSeparators::from_sample(&String::from(sample))
+        })
+        .unwrap_or_default()
+}
+
+/// The cluster the wallet says it's on. Phantom doesn't expose this, so it's
+/// best effort: a provider reporting `cluster` or `network` (plain like
+/// `"devnet"` or chain-style like `"solana:devnet"`) is read, anything else
+/// is `None`.
+pub fn wallet_cluster() -> Option<Cluster> {
+    let phantom = provider().ok()?;
+    ["cluster", "network"].iter().find_map(|key| {
+        let name = get(&phantom, key).ok()?.as_string()?;
+        match name.trim_start_matches("solana:") {
+            "mainnet" => Some(Cluster::Mainnet),
+            name => Cluster::from_name(name),
+        }
+    })
+}
+
+/// Writes `text` to the clipboard once the browser has accepted it, reporting
+/// why when it doesn't expose the async clipboard API (e.g. on plain http) or
+/// refuses the write (no permission, page not focused).
+pub async fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    let navigator = web_sys::window()
+        .ok_or_else(|| "No browser window".to_string())?
+        .navigator();
+    let clipboard = get(&navigator, "clipboard")
+        .map_err(|_| "Clipboard is not available in this browser".to_string())?;
+    let written = function(&clipboard, "writeText")?
+        .call1(&clipboard, &JsValue::from_str(text))
+        .map_err(|err| describe(&err))?
+        .dyn_into::<js_sys::Promise>()
+        .map_err(|_| "Clipboard write did not start".to_string())?;
+    wasm_bindgen_futures::JsFuture::from(written)
+        .await
+        .map(|_| ())
+        .map_err(|err| {
+            let name = Reflect::get(&err, &JsValue::from_str("name")).ok().and_then(|name| name.as_string());
+            clipboard_error(name.as_deref())
+        })
+}
+
+/// What to tell the user when `writeText` rejects, by the `DOMException` name.
+fn clipboard_error(name: Option<&str>) -> String {
+    match name {
+        Some("NotAllowedError") => "Copying was blocked; click into the page and allow clipboard access".to_string(),
+        Some("SecurityError") => "Copying is not allowed on this page".to_string(),
+        _ => "Could not copy to the clipboard".to_string(),
+    }
+}
+
+/// Resolves after `ms` milliseconds.
+pub async fn sleep(ms: u32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let window = web_sys::window().unwrap();
+        let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms as i32);
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Best-effort human readable text for a thrown JS value: the `message` of an
+/// `Error`, the value itself if it's a string, or a generic fallback.
+pub fn describe(err: &JsValue) -> String {
+    if let Some(message) = err.as_string() {
+        return message;
+    }
+    Reflect::get(err, &JsValue::from_str("message"))
+        .ok()
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| "Unexpected wallet error".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refused_permission_asks_for_access() {
+        assert!(clipboard_error(Some("NotAllowedError")).contains("allow clipboard access"));
+    }
+
+    #[test]
+    fn insecure_pages_are_named() {
+        assert_eq!(clipboard_error(Some("SecurityError")), "Copying is not allowed on this page");
+    }
+
+    #[test]
+    fn other_rejections_fall_back_to_a_generic_message() {
+        assert_eq!(clipboard_error(Some("DataError")), "Could not copy to the clipboard");
+        assert_eq!(clipboard_error(None), "Could not copy to the clipboard");
+    }
+
+    /// The reflection helpers need a JS engine; run with `wasm-pack test --headless --firefox`.
+    #[cfg(target_arch = "wasm32")]
+    mod browser {
+        use super::super::*;
+        use wasm_bindgen_test::wasm_bindgen_test;
+
+        fn object(key: &str, value: &JsValue) -> JsValue {
+            let target = js_sys::Object::new();
+            Reflect::set(&target, &JsValue::from_str(key), value).unwrap();
+            target.into()
+        }
+
+        #[wasm_bindgen_test]
+        fn get_reports_missing_properties() {
+            let target = object("present", &JsValue::from_f64(1.0));
+            assert_eq!(get(&target, "present").unwrap().as_f64(), Some(1.0));
+            assert_eq!(get(&target, "absent").unwrap_err(), "`absent` is not available");
+        }
+
+        #[wasm_bindgen_test]
+        fn get_keeps_null_as_a_value() {
+            let target = object("cleared", &JsValue::NULL);
+            assert!(get(&target, "cleared").unwrap().is_null());
+        }
+
+        #[wasm_bindgen_test]
+        fn get_reports_unreadable_targets() {
+            assert!(get(&JsValue::UNDEFINED, "anything").is_err());
+        }
+
+        #[wasm_bindgen_test]
+        fn function_rejects_non_callables() {
+            let target = object("connect", &JsValue::from_str("not callable"));
+            assert_eq!(function(&target, "connect").unwrap_err(), "`connect` is not a function");
+            assert_eq!(function(&target, "disconnect").unwrap_err(), "`disconnect` is not available");
+        }
+
+        #[wasm_bindgen_test]
+        fn describe_reads_strings_errors_and_falls_back() {
+            assert_eq!(describe(&JsValue::from_str("User rejected the request.")), "User rejected the request.");
+            assert_eq!(describe(&js_sys::Error::new("boom").into()), "boom");
+            assert_eq!(describe(&JsValue::from_f64(4001.0)), "Unexpected wallet error");
+        }
+    }
+}
@@ -1,13 +1,44 @@
 use wasm_bindgen::prelude::*;
 use yew::prelude::*;
+mod api;
 mod app;
 mod components;
+mod context;
+mod interop;
+mod rpc;
+mod storage;
+mod telemetry;
+mod transaction;
 mod utils;
+mod validation;
+
+/// Placeholder in `index.html` shown while the WASM bundle loads.
+const LOADER_ID: &str = "app-loading";
+
+const LOAD_FAILED_HTML: &str = r#"<div class="error-fallback">
+    <p>The app failed to start.</p>
+    <button type="button" onclick="window.location.reload()">Reload</button>
+</div>"#;
 
 #[wasm_bindgen(start)]
 pub fn run_app() -> Result<(), JsValue> {
-    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+    std::panic::set_hook(Box::new(|info| {
+        console_error_panic_hook::hook(info);
+        // A panic before the first render leaves the loader up; replace it so
+        // the page doesn't spin forever.
+        if let Some(loader) = loader() {
+            loader.set_inner_html(LOAD_FAILED_HTML);
+        }
+        components::error_boundary::show_fallback();
+    }));
     wasm_logger::init(wasm_logger::Config::default());
     yew::Renderer::<app::App>::new().render();
+    if let Some(loader) = loader() {
+        loader.remove();
+    }
     Ok(())
+}
+
+fn loader() -> Option<web_sys::Element> {
+    web_sys::window()?.document()?.get_element_by_id(LOADER_ID)
 }
\ No newline at end of file
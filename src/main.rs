@@ -1,8 +1,16 @@
 use wasm_bindgen::prelude::*;
 use yew::prelude::*;
+mod api;
 mod app;
 mod components;
+mod context;
+mod interop;
+mod rpc;
+mod storage;
+mod telemetry;
+mod transaction;
 mod utils;
+mod validation;
 
 #[wasm_bindgen(start)]
 pub fn run_app() -> Result<(), JsValue> {
@@ -0,0 +1,416 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortController, Request, RequestInit, Response};
+use js_sys::Reflect;
+use yew::Callback;
+use crate::interop;
+use crate::utils::{cluster_rpc_url, Cluster};
+
+/// How long one endpoint gets before the next one is tried.
+const ATTEMPT_TIMEOUT_MS: i32 = 8_000;
+/// Most endpoints tried for a single call, the active one included.
+const MAX_ENDPOINTS: usize = 3;
+/// Longest `Retry-After` waited out on the same endpoint; anything longer
+/// fails over instead.
+const MAX_RETRY_AFTER_SECS: u32 = 30;
+
+thread_local! {
+    static ACTIVE_ENDPOINTS: RefCell<HashMap<Cluster, usize>> = RefCell::new(HashMap::new());
+    static ENDPOINT_LISTENER: RefCell<Option<Callback<(Cluster, &'static str)>>> = RefCell::new(None);
+    static CUSTOM_ENDPOINT: RefCell<Option<String>> = RefCell::new(None);
+    static RATE_LIMIT_LISTENER: RefCell<Option<Callback<Option<u32>>>> = RefCell::new(None);
+}
+
+/// The endpoint calls on `cluster` currently go to.
+pub fn active_endpoint(cluster: Cluster) -> String {
+    if let Some(url) = custom_endpoint() {
+        return url;
+    }
+    let index = ACTIVE_ENDPOINTS.with(|active| active.borrow().get(&cluster).copied().unwrap_or(0));
+    cluster.rpc_urls().get(index).copied().unwrap_or_else(|| cluster_rpc_url(cluster)).to_string()
+}
+
+/// Sends every call to `url` instead of the public endpoints, without
+/// failover. `None` goes back to the defaults.
+pub fn set_custom_endpoint(url: Option<String>) {
+    CUSTOM_ENDPOINT.with(|custom| *custom.borrow_mut() = url);
+}
+
+fn custom_endpoint() -> Option<String> {
+    CUSTOM_ENDPOINT.with(|custom| custom.borrow().clone())
+}
+
+/// Registers `listener` to hear about every failover.
+pub fn on_endpoint_change(listener: Callback<(Cluster, &'static str)>) {
+    ENDPOINT_LISTENER.with(|current| *current.borrow_mut() = Some(listener));
+}
+
+/// Registers `listener` to hear `Some(seconds)` while a rate-limited request
+/// waits to be retried, and `None` once it goes out again.
+pub fn on_rate_limit(listener: Callback<Option<u32>>) {
+    RATE_LIMIT_LISTENER.with(|current| *current.borrow_mut() = Some(listener));
+}
+
+fn notify_rate_limit(wait: Option<u32>) {
+    RATE_LIMIT_LISTENER.with(|listener| {
+        if let Some(listener) = listener.borrow().as_ref() {
+            listener.emit(wait);
+        }
+    });
+}
+
+/// Seconds to wait from a `Retry-After` header, given as either a number of
+/// seconds or an HTTP date (relative to `now_ms`).
+pub fn parse_retry_after(value: &str, now_ms: f64) -> Option<u32> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u32>() {
+        return Some(seconds);
+    }
+    let date_ms = js_sys::Date::parse(value);
+    if date_ms.is_nan() {
+        return None;
+    }
+    Some(((date_ms - now_ms) / 1_000.0).ceil().max(0.0) as u32)
+}
+
+enum Attempt {
+    Done(JsValue),
+    /// HTTP 429, with the `Retry-After` delay if one was given.
+    RateLimited(Option<u32>),
+}
+
+/// POSTs to one endpoint, waiting out one short `Retry-After` before giving
+/// up on it.
+async fn post(rpc_url: &str, body: &str) -> Result<JsValue, JsValue> {
+    match post_once(rpc_url, body).await? {
+        Attempt::Done(json) => return Ok(json),
+        Attempt::RateLimited(Some(wait)) if wait <= MAX_RETRY_AFTER_SECS => {
+            notify_rate_limit(Some(wait));
+            interop::sleep(wait * 1_000).await;
+            notify_rate_limit(None);
+        }
+        Attempt::RateLimited(_) => return Err(JsValue::from_str("HTTP 429")),
+    }
+    match post_once(rpc_url, body).await? {
+        Attempt::Done(json) => Ok(json),
+        Attempt::RateLimited(_) => Err(JsValue::from_str("HTTP 429")),
+    }
+}
+
+/// POSTs to one endpoint, aborting after `ATTEMPT_TIMEOUT_MS`. Returns the
+/// parsed JSON-RPC envelope; transport failures and other non-2xx responses
+/// are errors.
+async fn post_once(rpc_url: &str, body: &str) -> Result<Attempt, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("No window"))?;
+    let controller = AbortController::new()?;
+    let mut opts = RequestInit::new();
+    opts.method("POST");
+    opts.body(Some(&JsValue::from_str(body)));
+    opts.signal(Some(&controller.signal()));
+
+    let request = Request::new_with_str_and_init(rpc_url, &opts)?;
+    request.headers().set("Content-Type", "application/json")?;
+
+    let abort = Closure::once_into_js(move || controller.abort());
+    let timeout = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        abort.unchecked_ref(),
+        ATTEMPT_TIMEOUT_MS,
+    )?;
+    let response = JsFuture::from(window.fetch_with_request(&request)).await;
+    window.clear_timeout_with_handle(timeout);
+
+    let response: Response = response?.dyn_into()?;
+    if response.status() == 429 {
+        let wait = response
+            .headers()
+            .get("Retry-After")
+            .ok()
+            .flatten()
+            .and_then(|value| parse_retry_after(&value, js_sys::Date::now()));
+        return Ok(Attempt::RateLimited(wait));
+    }
+    if !response.ok() {
+        return Err(JsValue::from_str(&format!("HTTP {}", response.status())));
+    }
+    Ok(Attempt::Done(JsFuture::from(response.json()?).await?))
+}
+
+/// Sends a JSON-RPC request on `cluster`, failing over to the next configured
+/// endpoint when the current one doesn't answer.
+async fn call(cluster: Cluster, method: &str, params: &str) -> Result<JsValue, JsValue> {
+    let body = format!(
+        r#"{{"jsonrpc":"2.0","id":1,"method":"{}","params":{}}}"#,
+        method, params
+    );
+    if let Some(url) = custom_endpoint() {
+        return result_of(post(&url, &body).await?);
+    }
+
+    let urls = cluster.rpc_urls();
+    let start = ACTIVE_ENDPOINTS.with(|active| active.borrow().get(&cluster).copied().unwrap_or(0));
+
+    let mut last_error = JsValue::from_str("No RPC endpoint configured");
+    for offset in 0..urls.len().min(MAX_ENDPOINTS) {
+        let index = (start + offset) % urls.len();
+        let json = match post(urls[index], &body).await {
+            Ok(json) => json,
+            Err(err) => {
+                last_error = err;
+                continue;
+            }
+        };
+
+        if index != start {
+            ACTIVE_ENDPOINTS.with(|active| active.borrow_mut().insert(cluster, index));
+            ENDPOINT_LISTENER.with(|listener| {
+                if let Some(listener) = listener.borrow().as_ref() {
+                    listener.emit((cluster, urls[index]));
+                }
+            });
+        }
+
+        return result_of(json);
+    }
+    Err(last_error)
+}
+
+/// The `result` of a JSON-RPC response, or its `error` as the failure.
+fn result_of(json: JsValue) -> Result<JsValue, JsValue> {
+    let error = Reflect::get(&json, &JsValue::from_str("error"))?;
+    if !error.is_undefined() {
+        return Err(error);
+    }
+    Reflect::get(&json, &JsValue::from_str("result"))
+}
+
+/// Looks up the decimals of `mint` with `getTokenSupply`.
+pub async fn get_token_decimals(cluster: Cluster, mint: &str) -> Result<u8, JsValue> {
+    Ok(get_token_supply(cluster, mint).await?.decimals)
+}
+
+/// Total supply of a mint, in base units.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenSupply {
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+pub async fn get_token_supply(cluster: Cluster, mint: &str) -> Result<TokenSupply, JsValue> {
+    let result = call(cluster, "getTokenSupply", &format!(r#"["{}"]"#, mint)).await?;
+    let value = Reflect::get(&result, &JsValue::from_str("value"))?;
+    let amount = Reflect::get(&value, &JsValue::from_str("amount"))?
+        .as_string()
+        .and_then(|amount| amount.parse().ok())
+        .ok_or_else(|| JsValue::from_str("Missing supply"))?;
+    let decimals = Reflect::get(&value, &JsValue::from_str("decimals"))?
+        .as_f64()
+        .map(|decimals| decimals as u8)
+        .ok_or_else(|| JsValue::from_str("Missing decimals"))?;
+    Ok(TokenSupply { amount, decimals })
+}
+
+/// The token account `owner` holds `mint` in, if any. With several accounts
+/// for the same mint, the one with the largest balance wins.
+pub async fn find_token_account(
+    cluster: Cluster,
+    owner: &str,
+    mint: &str,
+) -> Result<Option<TokenAccount>, JsValue> {
+    let params = format!(
+        r#"["{}",{{"mint":"{}"}},{{"encoding":"jsonParsed"}}]"#,
+        owner, mint
+    );
+    let result = call(cluster, "getTokenAccountsByOwner", &params).await?;
+    let accounts = js_sys::Array::from(&Reflect::get(&result, &JsValue::from_str("value"))?);
+    Ok(accounts
+        .iter()
+        .filter_map(|entry| parse_token_account(&entry))
+        .max_by_key(|account| account.amount))
+}
+
+pub async fn get_latest_blockhash(cluster: Cluster) -> Result<String, JsValue> {
+    let result = call(cluster, "getLatestBlockhash", "[]").await?;
+    let value = Reflect::get(&result, &JsValue::from_str("value"))?;
+    Reflect::get(&value, &JsValue::from_str("blockhash"))?
+        .as_string()
+        .ok_or_else(|| JsValue::from_str("Missing blockhash"))
+}
+
+/// Native SOL balance of `address`, in lamports.
+pub async fn get_balance(cluster: Cluster, address: &str) -> Result<u64, JsValue> {
+    let params = format!(r#"["{}"]"#, address);
+    let result = call(cluster, "getBalance", &params).await?;
+    Reflect::get(&result, &JsValue::from_str("value"))?
+        .as_f64()
+        .map(|lamports| lamports as u64)
+        .ok_or_else(|| JsValue::from_str("Missing balance"))
+}
+
+/// Where a submitted transaction stands, per `getSignatureStatuses`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Not seen yet, or seen but only processed.
+    Pending,
+    Confirmed,
+    Failed,
+}
+
+/// Searches the full transaction history, not just the node's recent status
+/// cache: a burn resumed after a reload can be older than that cache holds.
+pub async fn get_signature_status(cluster: Cluster, signature: &str) -> Result<SignatureStatus, JsValue> {
+    let params = format!(r#"[["{}"],{{"searchTransactionHistory":true}}]"#, signature);
+    let result = call(cluster, "getSignatureStatuses", &params).await?;
+    let status = Reflect::get(&Reflect::get(&result, &JsValue::from_str("value"))?, &JsValue::from_f64(0.0))?;
+    if status.is_null() || status.is_undefined() {
+        return Ok(SignatureStatus::Pending);
+    }
+    let err = Reflect::get(&status, &JsValue::from_str("err"))?;
+    if !err.is_null() && !err.is_undefined() {
+        return Ok(SignatureStatus::Failed);
+    }
+    match Reflect::get(&status, &JsValue::from_str("confirmationStatus"))?.as_string().as_deref() {
+        Some("confirmed") | Some("finalized") => Ok(SignatureStatus::Confirmed),
+        _ => Ok(SignatureStatus::Pending),
+    }
+}
+
+/// What `simulateTransaction` reported for a transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Simulation {
+    /// The transaction error, if it would have failed.
+    pub err: Option<String>,
+    pub units_consumed: Option<u64>,
+}
+
+/// Simulates a base58 wire transaction without checking signatures, against
+/// the latest blockhash.
+pub async fn simulate_transaction(cluster: Cluster, transaction: &str) -> Result<Simulation, JsValue> {
+    let params = format!(
+        r#"["{}",{{"encoding":"base58","sigVerify":false,"replaceRecentBlockhash":true}}]"#,
+        transaction
+    );
+    let result = call(cluster, "simulateTransaction", &params).await?;
+    let value = Reflect::get(&result, &JsValue::from_str("value"))?;
+    let err = Reflect::get(&value, &JsValue::from_str("err"))?;
+    Ok(Simulation {
+        err: if err.is_null() || err.is_undefined() {
+            None
+        } else {
+            Some(js_sys::JSON::stringify(&err).map(String::from).unwrap_or_else(|_| "unknown error".to_string()))
+        },
+        units_consumed: Reflect::get(&value, &JsValue::from_str("unitsConsumed"))?
+            .as_f64()
+            .map(|units| units as u64),
+    })
+}
+
+/// Whether `id` is a compressed NFT, via the DAS `getAsset` method. Plain
+/// public RPCs don't implement DAS, which surfaces as an error.
+pub async fn is_compressed_asset(cluster: Cluster, id: &str) -> Result<bool, JsValue> {
+    let result = call(cluster, "getAsset", &format!(r#"{{"id":"{}"}}"#, id)).await?;
+    let compression = Reflect::get(&result, &JsValue::from_str("compression"))?;
+    if compression.is_undefined() || compression.is_null() {
+        return Ok(false);
+    }
+    Ok(Reflect::get(&compression, &JsValue::from_str("compressed"))?.as_bool().unwrap_or(false))
+}
+
+/// Program that owns `address`, or `None` if the account doesn't exist.
+pub async fn get_account_owner(cluster: Cluster, address: &str) -> Result<Option<String>, JsValue> {
+    let params = format!(r#"["{}",{{"encoding":"base64"}}]"#, address);
+    let result = call(cluster, "getAccountInfo", &params).await?;
+    let value = Reflect::get(&result, &JsValue::from_str("value"))?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    Ok(Reflect::get(&value, &JsValue::from_str("owner"))?.as_string())
+}
+
+/// A token account as reported by `getTokenAccountsByOwner` with `jsonParsed`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenAccount {
+    pub address: String,
+    pub mint: String,
+    pub program: String,
+    /// Raw balance in base units.
+    pub amount: u64,
+    pub decimals: u8,
+    pub ui_amount: f64,
+    /// Frozen by the mint's freeze authority; burns from it fail.
+    pub frozen: bool,
+}
+
+fn parse_token_account(entry: &JsValue) -> Option<TokenAccount> {
+    let get = |value: &JsValue, key: &str| Reflect::get(value, &JsValue::from_str(key)).ok();
+    let account = get(entry, "account")?;
+    let info = get(&get(&get(&account, "data")?, "parsed")?, "info")?;
+    let token_amount = get(&info, "tokenAmount")?;
+
+    Some(TokenAccount {
+        address: get(entry, "pubkey")?.as_string()?,
+        mint: get(&info, "mint")?.as_string()?,
+        program: get(&account, "owner")?.as_string()?,
+        amount: get(&token_amount, "amount")?.as_string()?.parse().ok()?,
+        decimals: get(&token_amount, "decimals")?.as_f64()? as u8,
+        ui_amount: get(&token_amount, "uiAmount")?.as_f64().unwrap_or(0.0),
+        frozen: get(&info, "state").and_then(|state| state.as_string()).as_deref() == Some("frozen"),
+    })
+}
+
+/// Whether the token account at `address` is frozen, read fresh with
+/// `getAccountInfo`. A missing account isn't frozen.
+pub async fn is_token_account_frozen(cluster: Cluster, address: &str) -> Result<bool, JsValue> {
+    let params = format!(r#"["{}",{{"encoding":"jsonParsed"}}]"#, address);
+    let result = call(cluster, "getAccountInfo", &params).await?;
+    let value = Reflect::get(&result, &JsValue::from_str("value"))?;
+    if value.is_null() {
+        return Ok(false);
+    }
+    let info = Reflect::get(
+        &Reflect::get(&Reflect::get(&value, &JsValue::from_str("data"))?, &JsValue::from_str("parsed"))?,
+        &JsValue::from_str("info"),
+    )?;
+    Ok(Reflect::get(&info, &JsValue::from_str("state"))?.as_string().as_deref() == Some("frozen"))
+}
+
+/// Every token account of `owner` under `program_id`.
+pub async fn get_token_accounts(
+    cluster: Cluster,
+    owner: &str,
+    program_id: &str,
+) -> Result<Vec<TokenAccount>, JsValue> {
+    let params = format!(
+        r#"["{}",{{"programId":"{}"}},{{"encoding":"jsonParsed"}}]"#,
+        owner, program_id
+    );
+    let result = call(cluster, "getTokenAccountsByOwner", &params).await?;
+    let accounts = js_sys::Array::from(&Reflect::get(&result, &JsValue::from_str("value"))?);
+    Ok(accounts.iter().filter_map(|entry| parse_token_account(&entry)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // HTTP-date values go through `Date.parse` and need a JS runtime, so only
+    // the delay-seconds form is covered here.
+    #[test]
+    fn retry_after_reads_delay_seconds() {
+        assert_eq!(parse_retry_after("0", 0.0), Some(0));
+        assert_eq!(parse_retry_after("120", 0.0), Some(120));
+    }
+
+    #[test]
+    fn retry_after_ignores_surrounding_whitespace() {
+        assert_eq!(parse_retry_after(" 30 ", 0.0), Some(30));
+        assert_eq!(parse_retry_after("5\r\n", 0.0), Some(5));
+    }
+
+    #[test]
+    fn retry_after_seconds_do_not_depend_on_now() {
+        assert_eq!(parse_retry_after("7", 1_700_000_000_000.0), Some(7));
+    }
+}
@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use web_sys::Storage;
+
+const KEY_PREFIX: &str = "solana-burner:";
+
+thread_local! {
+    /// Stands in for `localStorage` when it's unavailable (private browsing,
+    /// disabled storage) or a write is rejected (quota exceeded).
+    static MEMORY: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageError {
+    Serialize(String),
+    Deserialize(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Serialize(err) => write!(f, "Could not save value: {}", err),
+            StorageError::Deserialize(err) => write!(f, "Could not read saved value: {}", err),
+        }
+    }
+}
+
+fn local_storage() -> Option<Storage> {
+    // Outside the browser (native unit tests) there's no `window` to ask, so
+    // everything lives in memory.
+    if cfg!(not(target_arch = "wasm32")) {
+        return None;
+    }
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+fn full_key(key: &str) -> String {
+    format!("{}{}", KEY_PREFIX, key)
+}
+
+/// Takes the storage to use so the memory fallback can be exercised without
+/// a browser.
+fn get_raw(storage: Option<&Storage>, key: &str) -> Option<String> {
+    let key = full_key(key);
+    if let Some(value) = MEMORY.with(|memory| memory.borrow().get(&key).cloned()) {
+        return Some(value);
+    }
+    storage?.get_item(&key).ok().flatten()
+}
+
+fn set_raw(storage: Option<&Storage>, key: &str, value: String) {
+    let key = full_key(key);
+    let stored = storage.map_or(false, |storage| storage.set_item(&key, &value).is_ok());
+    MEMORY.with(|memory| {
+        let mut memory = memory.borrow_mut();
+        if stored {
+            memory.remove(&key);
+        } else {
+            memory.insert(key, value);
+        }
+    });
+}
+
+/// Reads and deserializes `key`. A missing key, or storage that can't be
+/// accessed, is `Ok(None)`.
+pub fn get<T: DeserializeOwned>(key: &str) -> Result<Option<T>, StorageError> {
+    match get_raw(local_storage().as_ref(), key) {
+        Some(raw) => from_json(&raw).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Serializes and stores `value` under `key`, keeping it in memory for the
+/// session if `localStorage` refuses it.
+pub fn set<T: Serialize>(key: &str, value: &T) -> Result<(), StorageError> {
+    set_raw(local_storage().as_ref(), key, to_json(value)?);
+    Ok(())
+}
+
+pub fn remove(key: &str) {
+    let key = full_key(key);
+    MEMORY.with(|memory| memory.borrow_mut().remove(&key));
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(&key);
+    }
+}
+
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, StorageError> {
+    serde_json::to_string(value).map_err(|err| StorageError::Serialize(err.to_string()))
+}
+
+pub fn from_json<T: DeserializeOwned>(raw: &str) -> Result<T, StorageError> {
+    serde_json::from_str(raw).map_err(|err| StorageError::Deserialize(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Saved {
+        mint: String,
+        amount: u64,
+    }
+
+    #[test]
+    fn keys_are_prefixed() {
+        assert_eq!(full_key("settings"), "solana-burner:settings");
+        assert_eq!(full_key(""), KEY_PREFIX);
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let saved = Saved { mint: "mint".to_string(), amount: 42 };
+        let raw = to_json(&saved).unwrap();
+        assert_eq!(from_json::<Saved>(&raw), Ok(saved));
+    }
+
+    #[test]
+    fn unreadable_json_is_a_deserialize_error() {
+        assert!(matches!(from_json::<Saved>("{\"mint\":"), Err(StorageError::Deserialize(_))));
+    }
+
+    #[test]
+    fn falls_back_to_memory_without_local_storage() {
+        set_raw(None, "fallback", "value".to_string());
+        assert_eq!(get_raw(None, "fallback"), Some("value".to_string()));
+        // Kept under the prefixed key, like it would be in `localStorage`.
+        assert!(MEMORY.with(|memory| memory.borrow().contains_key("solana-burner:fallback")));
+        assert_eq!(get_raw(None, "other"), None);
+    }
+
+    #[test]
+    fn fallback_writes_replace_earlier_ones() {
+        set_raw(None, "replaced", "first".to_string());
+        set_raw(None, "replaced", "second".to_string());
+        assert_eq!(get_raw(None, "replaced"), Some("second".to_string()));
+    }
+}
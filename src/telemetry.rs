@@ -0,0 +1,46 @@
+use serde::de::IgnoredAny;
+use wasm_bindgen::prelude::*;
+use js_sys::Reflect;
+use crate::api::{self, ServiceStatus};
+use crate::context::Settings;
+use crate::utils::{fetch_json, Cluster};
+
+const TELEMETRY_PATH: &str = "/api/telemetry";
+
+/// Anonymous usage events. None of them carry addresses, mints, amounts or
+/// signatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    BurnSucceeded,
+    BurnFailed,
+}
+
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Event::BurnSucceeded => "burn_success",
+            Event::BurnFailed => "burn_failure",
+        }
+    }
+}
+
+/// Whether the browser asks not to be tracked.
+pub fn do_not_track() -> bool {
+    web_sys::window()
+        .and_then(|window| Reflect::get(&window.navigator(), &JsValue::from_str("doNotTrack")).ok())
+        .and_then(|value| value.as_string())
+        .map_or(false, |value| value == "1" || value == "yes")
+}
+
+/// Sends `event` if the user opted in, Do Not Track is off and the backend
+/// is up. Fire and forget: failures are ignored.
+pub fn record(settings: &Settings, service: &ServiceStatus, event: Event, cluster: Cluster) {
+    if !settings.telemetry || do_not_track() || !service.backend_available {
+        return;
+    }
+
+    let body = format!(r#"{{"event":"{}","cluster":"{}"}}"#, event.name(), cluster.name());
+    wasm_bindgen_futures::spawn_local(async move {
+        let _ = fetch_json::<IgnoredAny>(&api::api_url(TELEMETRY_PATH), "POST", Some(&body)).await;
+    });
+}
@@ -0,0 +1,368 @@
+use std::str::FromStr;
+use solana_program::hash::Hash;
+use solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::message::Message;
+use solana_program::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use crate::components::wallet::sign_and_send;
+use crate::interop;
+use crate::rpc;
+use crate::utils::Cluster;
+
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const FROZEN_MESSAGE: &str = "This token account is frozen and cannot be burned";
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// SPL Memo v2.
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+/// Compute units budgeted for a memo on top of the burns; the memo program
+/// charges per byte and per signer checked.
+pub const MEMO_COMPUTE_UNITS: u32 = 20_000;
+/// Rent-exempt reserve of a 165-byte token account, returned when it's closed.
+pub const TOKEN_ACCOUNT_RENT_LAMPORTS: u64 = 2_039_280;
+
+/// The program that owns a mint and its token accounts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TokenProgram {
+    #[default]
+    Legacy,
+    Token2022,
+}
+
+impl TokenProgram {
+    pub fn from_owner(owner: &str) -> Option<TokenProgram> {
+        match owner {
+            TOKEN_PROGRAM_ID => Some(TokenProgram::Legacy),
+            TOKEN_2022_PROGRAM_ID => Some(TokenProgram::Token2022),
+            _ => None,
+        }
+    }
+
+    pub fn id(&self) -> Pubkey {
+        let id = match self {
+            TokenProgram::Legacy => TOKEN_PROGRAM_ID,
+            TokenProgram::Token2022 => TOKEN_2022_PROGRAM_ID,
+        };
+        Pubkey::from_str(id).unwrap()
+    }
+}
+
+/// Everything needed to burn `amount` base units of `mint` held by `owner`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BurnRequest {
+    pub owner: String,
+    pub mint: String,
+    pub amount: u64,
+    pub decimals: u8,
+    pub token_program: TokenProgram,
+    /// Token account to burn from, as found on-chain. `None` falls back to
+    /// the owner's associated token account.
+    pub token_account: Option<String>,
+    pub priority_fee_micro_lamports: u64,
+    pub compute_unit_limit: u32,
+    /// Note recorded on-chain with the burn; see `utils::sanitize_memo`.
+    pub memo: Option<String>,
+}
+
+fn parse_pubkey(value: &str, what: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(value).map_err(|_| format!("Invalid {} address", what))
+}
+
+/// The token account burns and unwraps act on: the one the request was
+/// built from, or else the owner's associated token account for the mint.
+pub fn token_account_address(request: &BurnRequest) -> Result<Pubkey, String> {
+    if let Some(account) = &request.token_account {
+        return parse_pubkey(account, "token account");
+    }
+    let owner = parse_pubkey(&request.owner, "wallet")?;
+    let mint = parse_pubkey(&request.mint, "mint")?;
+    Ok(get_associated_token_address_with_program_id(&owner, &mint, &request.token_program.id()))
+}
+
+/// `BurnChecked` against the request's token account. The Token-2022
+/// builder accepts either program ID, so it covers legacy mints too.
+pub fn burn_instruction(request: &BurnRequest) -> Result<Instruction, String> {
+    let owner = parse_pubkey(&request.owner, "wallet")?;
+    let mint = parse_pubkey(&request.mint, "mint")?;
+    let program_id = request.token_program.id();
+    let token_account = token_account_address(request)?;
+
+    spl_token_2022::instruction::burn_checked(
+        &program_id,
+        &token_account,
+        &mint,
+        &owner,
+        &[],
+        request.amount,
+        request.decimals,
+    )
+    .map_err(|err| format!("Could not build burn instruction: {}", err))
+}
+
+/// Wrapped SOL is "burned" by unwrapping it instead: there's nothing to gain
+/// from destroying lamports when closing the account returns them.
+pub fn is_wrapped_sol(mint: &str) -> bool {
+    mint == WSOL_MINT
+}
+
+/// `CloseAccount` on the owner's wSOL account, sending the wrapped
+/// balance and the rent back to the owner as native SOL.
+pub fn unwrap_instruction(request: &BurnRequest) -> Result<Instruction, String> {
+    let owner = parse_pubkey(&request.owner, "wallet")?;
+    let program_id = request.token_program.id();
+    let token_account = token_account_address(request)?;
+
+    spl_token_2022::instruction::close_account(&program_id, &token_account, &owner, &owner, &[])
+        .map_err(|err| format!("Could not build unwrap instruction: {}", err))
+}
+
+/// SPL Memo carrying `memo`, signed by the owner so it's attributable.
+pub fn memo_instruction(owner: &str, memo: &str) -> Result<Instruction, String> {
+    let owner = parse_pubkey(owner, "wallet")?;
+    Ok(Instruction {
+        program_id: Pubkey::from_str(MEMO_PROGRAM_ID).unwrap(),
+        accounts: vec![AccountMeta::new_readonly(owner, true)],
+        data: memo.as_bytes().to_vec(),
+    })
+}
+
+/// `ComputeBudget` `SetComputeUnitPrice`.
+pub fn compute_unit_price_instruction(micro_lamports: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction {
+        program_id: Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID).unwrap(),
+        accounts: Vec::<AccountMeta>::new(),
+        data,
+    }
+}
+
+/// `ComputeBudget` `SetComputeUnitLimit`.
+pub fn compute_unit_limit_instruction(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction {
+        program_id: Pubkey::from_str(COMPUTE_BUDGET_PROGRAM_ID).unwrap(),
+        accounts: Vec::<AccountMeta>::new(),
+        data,
+    }
+}
+
+/// Unsigned message for `request`, paid for by the owner.
+pub fn burn_message(request: &BurnRequest, recent_blockhash: &str) -> Result<Message, String> {
+    batch_burn_message(std::slice::from_ref(request), recent_blockhash)
+}
+
+/// Unsigned message burning every request in one transaction. All requests
+/// must share an owner; the fee settings and memo of the first one apply and
+/// the compute limits add up. wSOL requests unwrap the whole account rather
+/// than burn.
+pub fn batch_burn_message(requests: &[BurnRequest], recent_blockhash: &str) -> Result<Message, String> {
+    let first = requests.first().ok_or_else(|| "Nothing to burn".to_string())?;
+    if requests.iter().any(|request| request.owner != first.owner) {
+        return Err("All burns in a batch must share an owner".to_string());
+    }
+    let payer = parse_pubkey(&first.owner, "wallet")?;
+    let blockhash = Hash::from_str(recent_blockhash).map_err(|_| "Invalid blockhash".to_string())?;
+
+    let memo = first.memo.as_deref().filter(|memo| !memo.is_empty());
+    let compute_unit_limit = requests
+        .iter()
+        .fold(0u32, |total, request| total.saturating_add(request.compute_unit_limit))
+        .saturating_add(if memo.is_some() { MEMO_COMPUTE_UNITS } else { 0 });
+    let mut instructions = vec![compute_unit_limit_instruction(compute_unit_limit)];
+    if first.priority_fee_micro_lamports > 0 {
+        instructions.push(compute_unit_price_instruction(first.priority_fee_micro_lamports));
+    }
+    for request in requests {
+        instructions.push(if is_wrapped_sol(&request.mint) {
+            unwrap_instruction(request)?
+        } else {
+            burn_instruction(request)?
+        });
+    }
+    if let Some(memo) = memo {
+        instructions.push(memo_instruction(&first.owner, memo)?);
+    }
+
+    Ok(Message::new_with_blockhash(&instructions, Some(&payer), &blockhash))
+}
+
+/// Base58 of the serialized message, the form Phantom's `signAndSendTransaction`
+/// request expects.
+pub fn encode_message(message: &Message) -> String {
+    bs58::encode(message.serialize()).into_string()
+}
+
+/// Base58 wire transaction for `message` with zeroed signatures, which is all
+/// `simulateTransaction` needs when signature checks are off.
+pub fn encode_unsigned_transaction(message: &Message) -> String {
+    let signers = message.header.num_required_signatures as usize;
+    // Compact-u16 signature count; a single byte below 128.
+    let mut bytes = vec![signers as u8];
+    bytes.resize(1 + signers * 64, 0);
+    bytes.extend_from_slice(&message.serialize());
+    bs58::encode(bytes).into_string()
+}
+
+/// Runs the burns through `simulateTransaction` without involving the wallet,
+/// returning the compute units they'd use.
+pub async fn simulate_burns(cluster: Cluster, requests: &[BurnRequest]) -> Result<u64, String> {
+    let blockhash = rpc::get_latest_blockhash(cluster)
+        .await
+        .map_err(|_| "Could not fetch a recent blockhash".to_string())?;
+    let message = batch_burn_message(requests, &blockhash)?;
+    let simulation = rpc::simulate_transaction(cluster, &encode_unsigned_transaction(&message))
+        .await
+        .map_err(|_| "Simulation request failed".to_string())?;
+    match simulation.err {
+        Some(err) => Err(format!("Simulation failed: {}", err)),
+        None => Ok(simulation.units_consumed.unwrap_or(0)),
+    }
+}
+
+/// Builds the burns into one transaction against a fresh blockhash and hands it
+/// to the wallet to sign and send.
+pub async fn submit_burns(cluster: Cluster, requests: &[BurnRequest]) -> Result<String, String> {
+    for request in requests {
+        let account = token_account_address(request)?.to_string();
+        if rpc::is_token_account_frozen(cluster, &account).await.unwrap_or(false) {
+            return Err(FROZEN_MESSAGE.to_string());
+        }
+    }
+    let blockhash = rpc::get_latest_blockhash(cluster)
+        .await
+        .map_err(|_| "Could not fetch a recent blockhash".to_string())?;
+    let message = batch_burn_message(requests, &blockhash)?;
+    sign_and_send(&encode_message(&message))
+        .await
+        .map_err(|err| format!("Failed to burn tokens: {}", err))
+}
+
+/// Default delay between `getSignatureStatuses` polls.
+pub const CONFIRM_POLL_INTERVAL_MS: u32 = 2_000;
+/// Shortest poll delay accepted, so a low setting can't hammer the RPC.
+pub const MIN_CONFIRM_POLL_INTERVAL_MS: u32 = 500;
+/// Default number of polls before giving up on observing a confirmation
+/// (about 90 seconds at the default interval).
+pub const CONFIRM_MAX_ATTEMPTS: u32 = 45;
+
+/// Polls until `signature` is confirmed or fails, every `interval_ms` (never
+/// less than `MIN_CONFIRM_POLL_INTERVAL_MS`) for up to `max_attempts` polls.
+/// `on_slow` fires once when polling has gone on for `slow_after_secs`
+/// without an answer; transient RPC errors count as "still pending" rather
+/// than failure.
+pub async fn confirm_signature(
+    cluster: Cluster,
+    signature: &str,
+    interval_ms: u32,
+    max_attempts: u32,
+    slow_after_secs: u32,
+    on_slow: impl FnOnce(),
+) -> Result<(), String> {
+    let interval_ms = interval_ms.max(MIN_CONFIRM_POLL_INTERVAL_MS);
+    let mut on_slow = Some(on_slow);
+    for attempt in 1..=max_attempts.max(1) {
+        interop::sleep(interval_ms).await;
+        match rpc::get_signature_status(cluster, signature).await {
+            Ok(rpc::SignatureStatus::Confirmed) => return Ok(()),
+            Ok(rpc::SignatureStatus::Failed) => return Err("Burn transaction failed on-chain".to_string()),
+            Ok(rpc::SignatureStatus::Pending) | Err(_) => {}
+        }
+        if attempt.saturating_mul(interval_ms) >= slow_after_secs.saturating_mul(1_000) {
+            if let Some(on_slow) = on_slow.take() {
+                on_slow();
+            }
+        }
+    }
+    Err("Could not confirm the burn in time — check the explorer before retrying".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::sanitize_memo;
+
+    const OWNER: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const BLOCKHASH: &str = "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N";
+
+    fn request(memo: Option<String>) -> BurnRequest {
+        BurnRequest {
+            owner: OWNER.to_string(),
+            mint: MINT.to_string(),
+            amount: 1_000_000,
+            decimals: 6,
+            token_program: TokenProgram::Legacy,
+            token_account: None,
+            priority_fee_micro_lamports: 0,
+            compute_unit_limit: 20_000,
+            memo,
+        }
+    }
+
+    fn has_memo(message: &Message) -> bool {
+        let memo_program = Pubkey::from_str(MEMO_PROGRAM_ID).unwrap();
+        message
+            .instructions
+            .iter()
+            .any(|instruction| message.account_keys[instruction.program_id_index as usize] == memo_program)
+    }
+
+    #[test]
+    fn empty_memos_add_no_memo_instruction() {
+        let blank = burn_message(&request(sanitize_memo(" \n ")), BLOCKHASH).unwrap();
+        assert!(!has_memo(&blank));
+        let empty = burn_message(&request(Some(String::new())), BLOCKHASH).unwrap();
+        assert!(!has_memo(&empty));
+        let memo = burn_message(&request(sanitize_memo("gm")), BLOCKHASH).unwrap();
+        assert!(has_memo(&memo));
+    }
+
+    fn request_with_limit(compute_unit_limit: u32) -> BurnRequest {
+        BurnRequest { compute_unit_limit, ..request(None) }
+    }
+
+    /// The limit set by the first instruction, `SetComputeUnitLimit`.
+    fn compute_unit_limit(message: &Message) -> u32 {
+        let data = &message.instructions[0].data;
+        assert_eq!(data[0], 2);
+        u32::from_le_bytes([data[1], data[2], data[3], data[4]])
+    }
+
+    #[test]
+    fn batch_compute_limits_add_up() {
+        let requests = [request_with_limit(20_000), request_with_limit(30_000), request_with_limit(5_000)];
+        let message = batch_burn_message(&requests, BLOCKHASH).unwrap();
+        assert_eq!(compute_unit_limit(&message), 55_000);
+    }
+
+    #[test]
+    fn batch_memo_adds_its_compute_units() {
+        let requests = [
+            BurnRequest { memo: sanitize_memo("gm"), ..request_with_limit(20_000) },
+            request_with_limit(20_000),
+        ];
+        let message = batch_burn_message(&requests, BLOCKHASH).unwrap();
+        assert_eq!(compute_unit_limit(&message), 40_000 + MEMO_COMPUTE_UNITS);
+        assert!(has_memo(&message));
+    }
+
+    #[test]
+    fn batch_compute_limit_saturates() {
+        let requests = [
+            BurnRequest { memo: sanitize_memo("gm"), ..request_with_limit(u32::MAX - 1) },
+            request_with_limit(u32::MAX),
+        ];
+        let message = batch_burn_message(&requests, BLOCKHASH).unwrap();
+        assert_eq!(compute_unit_limit(&message), u32::MAX);
+    }
+
+    #[test]
+    fn batch_needs_one_shared_owner() {
+        assert!(batch_burn_message(&[], BLOCKHASH).is_err());
+        let other = BurnRequest { owner: MINT.to_string(), ..request(None) };
+        assert!(batch_burn_message(&[request(None), other], BLOCKHASH).is_err());
+    }
+}
@@ -1,11 +1,1112 @@
+use std::fmt;
+use serde::de::DeserializeOwned;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{AbortController, Request, RequestInit, Response};
+
+/// Version and provenance of the running build, for matching bug reports to
+/// the exact code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    /// Short git hash; `None` when built outside a git checkout.
+    pub git_hash: Option<&'static str>,
+    /// Unix seconds.
+    pub built_at: Option<u64>,
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.version)?;
+        if let Some(hash) = self.git_hash {
+            write!(f, " ({})", hash)?;
+        }
+        if let Some(built_at) = self.built_at {
+            let date: String = js_sys::Date::new(&((built_at * 1_000) as f64).into()).to_iso_string().into();
+            write!(f, " built {}", &date[..10])?;
+        }
+        Ok(())
+    }
+}
+
+/// Build details embedded by `build.rs`.
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        git_hash: option_env!("BURNER_GIT_HASH").filter(|hash| !hash.is_empty()),
+        built_at: option_env!("BURNER_BUILD_TIMESTAMP")
+            .and_then(|secs| secs.parse().ok())
+            .filter(|secs| *secs > 0),
+    }
+}
 
 #[wasm_bindgen]
 pub fn format_transaction_signature(signature: &str) -> String {
-    format!("{}...{}", &signature[0..6], &signature[signature.len()-6..])
+    abbreviate(signature)
 }
 
 #[wasm_bindgen]
 pub fn validate_amount(amount: f64) -> bool {
     amount > 0.0
 }
+
+#[wasm_bindgen]
+pub fn is_valid_address(address: &str) -> bool {
+    (32..=MAX_ADDRESS_LEN).contains(&address.len())
+        && address.chars().all(|c| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l'))
+}
+
+#[wasm_bindgen]
+pub fn is_valid_mint(mint: &str) -> bool {
+    is_valid_address(mint)
+}
+
+/// Longest base58 string an address or signature can be.
+pub const MAX_ADDRESS_LEN: usize = 44;
+pub const MAX_SIGNATURE_LEN: usize = 88;
+
+/// A base58 transaction signature (64 bytes, so 64–88 characters).
+#[wasm_bindgen]
+pub fn is_valid_signature(signature: &str) -> bool {
+    (64..=MAX_SIGNATURE_LEN).contains(&signature.len())
+        && signature.chars().all(|c| c.is_ascii_alphanumeric() && !matches!(c, '0' | 'O' | 'I' | 'l'))
+}
+
+/// Shortens `value` to at most `max_chars` characters by cutting out the
+/// middle, so oversized or hostile strings can't stretch the layout.
+pub fn truncate_middle(value: &str, max_chars: usize) -> String {
+    let len = value.chars().count();
+    if len <= max_chars {
+        return value.to_string();
+    }
+    let keep = max_chars.saturating_sub(1);
+    let head: String = value.chars().take(keep - keep / 2).collect();
+    let tail: String = value.chars().skip(len - keep / 2).collect();
+    format!("{}…{}", head, tail)
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Cluster {
+    #[default]
+    Mainnet,
+    Devnet,
+    Testnet,
+}
+
+impl Cluster {
+    pub const ALL: [Cluster; 3] = [Cluster::Mainnet, Cluster::Devnet, Cluster::Testnet];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Cluster::Mainnet => "mainnet-beta",
+            Cluster::Devnet => "devnet",
+            Cluster::Testnet => "testnet",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Cluster> {
+        Cluster::ALL.into_iter().find(|cluster| cluster.name() == name)
+    }
+
+    /// Public RPC endpoints in the order they're tried; the first is the
+    /// primary, the rest are fallbacks.
+    pub fn rpc_urls(&self) -> &'static [&'static str] {
+        match self {
+            Cluster::Mainnet => &[
+                "https://api.mainnet-beta.solana.com",
+                "https://solana-rpc.publicnode.com",
+                "https://rpc.ankr.com/solana",
+            ],
+            Cluster::Devnet => &[
+                "https://api.devnet.solana.com",
+                "https://rpc.ankr.com/solana_devnet",
+            ],
+            Cluster::Testnet => &["https://api.testnet.solana.com"],
+        }
+    }
+}
+
+/// Canonical RPC endpoint for `cluster`, the first of its configured URLs.
+pub fn cluster_rpc_url(cluster: Cluster) -> &'static str {
+    cluster.rpc_urls()[0]
+}
+
+/// Block explorer that signature and address links open in.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Explorer {
+    #[default]
+    SolanaExplorer,
+    Solscan,
+    SolanaFm,
+}
+
+impl Explorer {
+    pub const ALL: [Explorer; 3] = [Explorer::SolanaExplorer, Explorer::Solscan, Explorer::SolanaFm];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Explorer::SolanaExplorer => "solana-explorer",
+            Explorer::Solscan => "solscan",
+            Explorer::SolanaFm => "solanafm",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Explorer> {
+        Explorer::ALL.into_iter().find(|explorer| explorer.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Explorer::SolanaExplorer => "Solana Explorer",
+            Explorer::Solscan => "Solscan",
+            Explorer::SolanaFm => "SolanaFM",
+        }
+    }
+
+    /// Value of the `cluster` query parameter, `None` where the explorer
+    /// defaults to the cluster. SolanaFM names every cluster, mainnet too.
+    fn cluster_param(&self, cluster: Cluster) -> Option<&'static str> {
+        match (self, cluster) {
+            (Explorer::SolanaFm, Cluster::Mainnet) => Some("mainnet-alpha"),
+            (Explorer::SolanaFm, Cluster::Devnet) => Some("devnet-solana"),
+            (Explorer::SolanaFm, Cluster::Testnet) => Some("testnet-solana"),
+            (_, Cluster::Mainnet) => None,
+            (_, cluster) => Some(cluster.name()),
+        }
+    }
+
+    fn link(&self, path: &str, id: &str, cluster: Cluster) -> String {
+        let base = match self {
+            Explorer::SolanaExplorer => "https://explorer.solana.com",
+            Explorer::Solscan => "https://solscan.io",
+            Explorer::SolanaFm => "https://solana.fm",
+        };
+        match self.cluster_param(cluster) {
+            Some(param) => format!("{}/{}/{}?cluster={}", base, path, id, param),
+            None => format!("{}/{}/{}", base, path, id),
+        }
+    }
+}
+
+/// `explorer`'s page for a transaction signature on `cluster`.
+#[wasm_bindgen]
+pub fn explorer_url(signature: &str, cluster: Cluster, explorer: Explorer) -> String {
+    explorer.link("tx", signature, cluster)
+}
+
+/// `explorer`'s page for an account (wallet, mint or token account).
+#[wasm_bindgen]
+pub fn explorer_address_url(address: &str, cluster: Cluster, explorer: Explorer) -> String {
+    let path = match explorer {
+        Explorer::Solscan => "account",
+        Explorer::SolanaExplorer | Explorer::SolanaFm => "address",
+    };
+    explorer.link(path, address, cluster)
+}
+
+/// Characters kept from each end by [`abbreviate`].
+const ABBREVIATED_SIDE: usize = 6;
+
+/// Short form of an address or signature: the first and last six characters.
+/// Strings of twelve characters or fewer are shown whole, as the two ends
+/// would overlap and repeat characters.
+pub fn abbreviate(value: &str) -> String {
+    let len = value.chars().count();
+    if len <= 2 * ABBREVIATED_SIDE {
+        return value.to_string();
+    }
+    let head: String = value.chars().take(ABBREVIATED_SIDE).collect();
+    let tail: String = value.chars().skip(len - ABBREVIATED_SIDE).collect();
+    format!("{}...{}", head, tail)
+}
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+pub const BASE_FEE_LAMPORTS: u64 = 5_000;
+/// Compute units requested for a burn; `BurnChecked` plus the budget
+/// instructions use well under this.
+pub const DEFAULT_COMPUTE_UNIT_LIMIT: u32 = 20_000;
+/// Highest custom priority fee accepted, in micro-lamports per compute unit
+/// (100 lamports per unit, far above what congestion ever calls for).
+pub const MAX_CUSTOM_PRIORITY_FEE: u64 = 100_000_000;
+
+/// Compute-unit price attached to the burn through a `ComputeBudget` instruction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PriorityFee {
+    #[default]
+    None,
+    Normal,
+    High,
+    Custom(u64),
+}
+
+impl PriorityFee {
+    pub fn micro_lamports(&self) -> u64 {
+        match self {
+            PriorityFee::None => 0,
+            PriorityFee::Normal => 10_000,
+            PriorityFee::High => 100_000,
+            PriorityFee::Custom(micro_lamports) => *micro_lamports,
+        }
+    }
+
+    /// A custom fee as typed, capped at [`MAX_CUSTOM_PRIORITY_FEE`]. Digits
+    /// too many to fit a `u64` are well over the cap too; anything else is 0.
+    pub fn custom(value: &str) -> Self {
+        let value = value.trim();
+        let micro_lamports = match value.parse::<u64>() {
+            Ok(micro_lamports) => micro_lamports,
+            Err(_) if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => MAX_CUSTOM_PRIORITY_FEE,
+            Err(_) => 0,
+        };
+        PriorityFee::Custom(micro_lamports.min(MAX_CUSTOM_PRIORITY_FEE))
+    }
+
+    /// Priority fee for `compute_units`, rounded up to whole lamports. Worked
+    /// out in `u128` so no fee and limit can overflow; saturates at `u64::MAX`.
+    pub fn lamports(&self, compute_units: u64) -> u64 {
+        let micro_lamports = self.micro_lamports() as u128 * compute_units as u128;
+        u64::try_from(micro_lamports.div_ceil(1_000_000)).unwrap_or(u64::MAX)
+    }
+}
+
+pub fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL as f64
+}
+
+/// "just now", "5 minutes ago", "3 days ago" for a Unix millis timestamp.
+/// A timestamp ahead of `now_ms` (the clock moved back, or the record came
+/// from a device with a faster clock) reads as "just now" rather than a
+/// negative age.
+pub fn format_relative_time(timestamp_ms: f64, now_ms: f64) -> String {
+    const UNITS: [(f64, &str); 4] = [(86_400.0, "day"), (3_600.0, "hour"), (60.0, "minute"), (1.0, "second")];
+
+    let seconds = ((now_ms - timestamp_ms) / 1_000.0).floor();
+    if !seconds.is_finite() || seconds < 10.0 {
+        return "just now".to_string();
+    }
+    let (size, unit) = UNITS
+        .iter()
+        .find(|(size, _)| seconds >= *size)
+        .copied()
+        .unwrap_or(UNITS[3]);
+    let count = (seconds / size).floor() as u64;
+    format!("{} {}{} ago", count, unit, if count == 1 { "" } else { "s" })
+}
+
+/// How a UI amount that falls between two base units is resolved.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Never exceeds the entered amount, so "Max" can't overshoot the balance.
+    #[default]
+    Floor,
+    Round,
+    Ceil,
+}
+
+impl RoundingMode {
+    pub const ALL: [RoundingMode; 3] = [RoundingMode::Floor, RoundingMode::Round, RoundingMode::Ceil];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RoundingMode::Floor => "floor",
+            RoundingMode::Round => "round",
+            RoundingMode::Ceil => "ceil",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<RoundingMode> {
+        RoundingMode::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+}
+
+pub fn ui_amount_to_base_units(amount: f64, decimals: u8, mode: RoundingMode) -> u64 {
+    let scaled = amount * 10f64.powi(decimals as i32);
+    // Undo representation error (0.29 * 100 == 28.999999999999996) before
+    // rounding in a direction.
+    let nearest = scaled.round();
+    let scaled = if (scaled - nearest).abs() < 1e-6 { nearest } else { scaled };
+    match mode {
+        RoundingMode::Floor => scaled.floor() as u64,
+        RoundingMode::Round => scaled.round() as u64,
+        RoundingMode::Ceil => scaled.ceil() as u64,
+    }
+}
+
+/// How a locale writes numbers: its decimal mark and, if it groups
+/// thousands, the grouping mark.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumberSeparators {
+    pub decimal: char,
+    pub grouping: Option<char>,
+}
+
+impl Default for NumberSeparators {
+    fn default() -> Self {
+        Self { decimal: '.', grouping: Some(',') }
+    }
+}
+
+impl NumberSeparators {
+    /// Reads the marks off `12345.6` as the locale formatted it, e.g.
+    /// `"12,345.6"`, `"12.345,6"` or `"12 345,6"`. Falls back to the default
+    /// when the sample doesn't look like that.
+    pub fn from_sample(sample: &str) -> Self {
+        let chars: Vec<char> = sample.chars().collect();
+        let decimal = match chars.len().checked_sub(2).map(|i| chars[i]) {
+            Some(c) if !c.is_ascii_digit() => c,
+            _ => return Self::default(),
+        };
+        let grouping = chars.get(2).copied().filter(|c| !c.is_ascii_digit() && *c != decimal);
+        Self { decimal, grouping }
+    }
+
+    /// Turns what the user typed into the canonical `1234.5` form that
+    /// parsing expects, or `None` when it could be read more than one way.
+    /// Grouping marks (and spaces) must split the whole part into threes
+    /// after a first group that doesn't start with 0, so `"1.2345"` or
+    /// `"0.250"` in a dot-grouping locale are refused rather than guessed
+    /// at. Comma-decimal locales that don't group with dots take a dot as
+    /// the decimal mark, so pasted amounts like `"0.25"` still work there.
+    pub fn normalize(&self, input: &str) -> Option<String> {
+        let input = input.trim();
+        let is_group = |c: char| Some(c) == self.grouping || matches!(c, ' ' | '\u{a0}' | '\u{202f}');
+        let decimal = if input.contains(self.decimal) || self.grouping == Some('.') {
+            self.decimal
+        } else {
+            '.'
+        };
+        let (whole, fraction) = match input.split_once(decimal) {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (input, None),
+        };
+        if fraction.map_or(false, |fraction| fraction.chars().any(is_group)) {
+            return None;
+        }
+
+        let mut groups = whole.split(is_group);
+        let first = groups.next().unwrap_or("");
+        let rest: Vec<&str> = groups.collect();
+        if !rest.is_empty() {
+            let digits = |group: &str| group.chars().all(|c| c.is_ascii_digit());
+            let first_ok = (1..=3).contains(&first.len()) && digits(first) && !first.starts_with('0');
+            if !first_ok || !rest.iter().all(|group| group.len() == 3 && digits(group)) {
+                return None;
+            }
+        }
+        let mut canonical: String = std::iter::once(first).chain(rest).collect();
+        if let Some(fraction) = fraction {
+            canonical.push('.');
+            canonical.push_str(fraction);
+        }
+        Some(canonical)
+    }
+
+    /// The canonical `amount` written with the locale's decimal mark, for
+    /// showing back in the input.
+    pub fn localize(&self, amount: &str) -> String {
+        amount.replace('.', &self.decimal.to_string())
+    }
+}
+
+/// `url` if it's a plain `https://` URL that's safe to put in an `src`
+/// attribute; anything else (other schemes, `data:`, whitespace, quotes or
+/// brackets that hint at injection) is dropped.
+pub fn https_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let rest = url.strip_prefix("https://")?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let suspicious = |c: char| c.is_whitespace() || c.is_control() || "\"'<>\\`".contains(c);
+    if host.is_empty() || url.chars().any(suspicious) {
+        return None;
+    }
+    Some(url.to_string())
+}
+
+/// Cuts fractional digits beyond `decimals` from a plain decimal string as it
+/// is typed, returning the kept text and whether anything was cut. Other
+/// forms (exponents, signs) pass through for validation to deal with.
+pub fn limit_decimal_places(amount: &str, decimals: u8) -> (String, bool) {
+    match amount.split_once('.') {
+        Some((whole, fraction)) if fraction.chars().all(|c| c.is_ascii_digit()) => {
+            let kept: String = fraction.chars().take(decimals as usize).collect();
+            let cut = kept.len() < fraction.len() || (decimals == 0 && amount.contains('.'));
+            if decimals == 0 {
+                (whole.to_string(), cut)
+            } else {
+                (format!("{}.{}", whole, kept), cut)
+            }
+        }
+        _ => (amount.to_string(), false),
+    }
+}
+
+/// Exact base units for a plain decimal string like `"1234.000000000000000001"`,
+/// without going through `f64`. Digits beyond `decimals` are rounded per
+/// `mode`. Returns `None` for anything else (signs, exponents, overflow).
+pub fn decimal_str_to_base_units(amount: &str, decimals: u8, mode: RoundingMode) -> Option<u64> {
+    let amount = amount.trim();
+    let (whole, fraction) = match amount.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (amount, ""),
+    };
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let decimals = decimals as usize;
+    let (kept, dropped) = fraction.split_at(fraction.len().min(decimals));
+    let mut units: u64 = 0;
+    for digit in whole.chars().chain(kept.chars()).chain(std::iter::repeat('0').take(decimals - kept.len())) {
+        units = units.checked_mul(10)?.checked_add(digit.to_digit(10)? as u64)?;
+    }
+
+    let round_up = match mode {
+        RoundingMode::Floor => false,
+        RoundingMode::Round => dropped.chars().next().map_or(false, |digit| digit >= '5'),
+        RoundingMode::Ceil => dropped.chars().any(|digit| digit != '0'),
+    };
+    if round_up {
+        units = units.checked_add(1)?;
+    }
+    Some(units)
+}
+
+/// Exact decimal string for `units` base units, e.g. `1500` with 3 decimals
+/// is `"1.5"`. The inverse of [`decimal_str_to_base_units`].
+pub fn base_units_to_decimal_str(units: u64, decimals: u8) -> String {
+    let digits = format!("{:0>width$}", units, width = decimals as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Base units for an amount typed by the user: exact for plain decimals, with
+/// the `f64` conversion as a fallback for forms like `1e-6`.
+pub fn amount_to_base_units(amount: &str, decimals: u8, mode: RoundingMode) -> Option<u64> {
+    decimal_str_to_base_units(amount, decimals, mode).or_else(|| {
+        amount
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|amount| amount.is_finite() && *amount >= 0.0)
+            .map(|amount| ui_amount_to_base_units(amount, decimals, mode))
+    })
+}
+
+/// Whether `amount` is above zero but less than one base unit at `decimals`,
+/// e.g. `0.0000001` for a 6-decimal token, which would burn nothing.
+pub fn is_below_smallest_unit(amount: &str, decimals: u8) -> bool {
+    amount.trim().parse::<f64>().map_or(false, |value| value > 0.0)
+        && amount_to_base_units(amount, decimals, RoundingMode::Floor) == Some(0)
+}
+
+/// Whether `units` differs from the typed `amount` by more than rounding to
+/// `decimals` places can explain. A last check before a burn, so a conversion
+/// bug is caught before it destroys the wrong amount.
+pub fn amount_diverges(amount: &str, units: u64, decimals: u8) -> bool {
+    let floor = decimal_str_to_base_units(amount, decimals, RoundingMode::Floor);
+    let ceil = decimal_str_to_base_units(amount, decimals, RoundingMode::Ceil);
+    if let (Some(floor), Some(ceil)) = (floor, ceil) {
+        return units < floor || units > ceil;
+    }
+    // Forms like `1e-6` only convert through f64; allow one base unit of
+    // rounding plus float noise.
+    match amount.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() => {
+            let scale = 10f64.powi(decimals as i32);
+            (units as f64 - value * scale).abs() > 1.0 + 1e-6
+        }
+        _ => true,
+    }
+}
+
+/// Longest memo attached to a burn, in characters.
+pub const MAX_MEMO_CHARS: usize = 200;
+/// Longest memo in UTF-8 bytes, what it actually costs in the transaction.
+pub const MAX_MEMO_BYTES: usize = 256;
+
+/// A user's memo made safe to put on-chain: control characters become
+/// spaces, whitespace runs collapse and it's cut to `MAX_MEMO_CHARS`, and
+/// further on a char boundary to `MAX_MEMO_BYTES` for multi-byte text.
+/// `None` when nothing is left.
+pub fn sanitize_memo(memo: &str) -> Option<String> {
+    let cleaned: String = memo
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut bytes = 0;
+    let memo: String = cleaned
+        .chars()
+        .take(MAX_MEMO_CHARS)
+        .take_while(|c| {
+            bytes += c.len_utf8();
+            bytes <= MAX_MEMO_BYTES
+        })
+        .collect();
+    let memo = memo.trim_end();
+    (!memo.is_empty()).then(|| memo.to_string())
+}
+
+/// Whether burning `units` destroys at least `threshold_percent` of a mint's
+/// `supply`, which is rarely what anyone but a mistaken project owner means.
+pub fn is_near_full_supply(units: u64, supply: u64, threshold_percent: f64) -> bool {
+    supply > 0 && units as f64 / supply as f64 * 100.0 >= threshold_percent
+}
+
+/// `value` with at most `decimals` fractional digits and no trailing zeros.
+pub fn format_amount(value: f64, decimals: u8) -> String {
+    let formatted = format!("{:.*}", decimals as usize, value);
+    if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        formatted
+    }
+}
+
+/// How long [`fetch_json`] waits for a response.
+const FETCH_TIMEOUT_MS: i32 = 10_000;
+
+/// Why a [`fetch_json`] request failed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AppError {
+    /// The request never got a response: offline, CORS, DNS, ...
+    Network(String),
+    Timeout,
+    /// The server answered with a non-2xx status.
+    Http(u16),
+    /// The body wasn't the JSON the caller expected.
+    Decode(String),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Network(message) => write!(f, "Network error: {}", message),
+            AppError::Timeout => write!(f, "Request timed out"),
+            AppError::Http(status) => write!(f, "Server responded with HTTP {}", status),
+            AppError::Decode(message) => write!(f, "Unexpected response: {}", message),
+        }
+    }
+}
+
+/// Sends `method` to `url` with an optional JSON `body` and decodes the JSON
+/// response as `T`. An empty body decodes as `null`, so `Option<_>` and
+/// `serde::de::IgnoredAny` work for endpoints that return nothing.
+pub async fn fetch_json<T: DeserializeOwned>(url: &str, method: &str, body: Option<&str>) -> Result<T, AppError> {
+    let js_error = |err: JsValue| AppError::Network(crate::interop::describe(&err));
+    let response = fetch_response(url, method, body).await?;
+    let text = JsFuture::from(response.text().map_err(js_error)?)
+        .await
+        .map_err(js_error)?
+        .as_string()
+        .unwrap_or_default();
+    let text = if text.trim().is_empty() { "null" } else { text.as_str() };
+    serde_json::from_str(text).map_err(|err| AppError::Decode(err.to_string()))
+}
+
+/// Sends `method` to `url` like [`fetch_json`] and returns the 2xx response
+/// with its body unread, for callers that only care that it succeeded.
+pub async fn fetch_response(url: &str, method: &str, body: Option<&str>) -> Result<Response, AppError> {
+    let js_error = |err: JsValue| AppError::Network(crate::interop::describe(&err));
+    let window = web_sys::window().ok_or_else(|| AppError::Network("No window".to_string()))?;
+    let controller = AbortController::new().map_err(js_error)?;
+    let mut opts = RequestInit::new();
+    opts.method(method);
+    opts.signal(Some(&controller.signal()));
+    if let Some(body) = body {
+        opts.body(Some(&JsValue::from_str(body)));
+    }
+    let request = Request::new_with_str_and_init(url, &opts).map_err(js_error)?;
+    if body.is_some() {
+        request.headers().set("Content-Type", "application/json").map_err(js_error)?;
+    }
+
+    let timed_out = std::rc::Rc::new(std::cell::Cell::new(false));
+    let abort = {
+        let timed_out = timed_out.clone();
+        Closure::once_into_js(move || {
+            timed_out.set(true);
+            controller.abort();
+        })
+    };
+    let timeout = window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(abort.unchecked_ref(), FETCH_TIMEOUT_MS)
+        .map_err(js_error)?;
+    let response = JsFuture::from(window.fetch_with_request(&request)).await;
+    window.clear_timeout_with_handle(timeout);
+
+    let response: Response = match response {
+        Ok(response) => response.dyn_into().map_err(js_error)?,
+        Err(_) if timed_out.get() => return Err(AppError::Timeout),
+        Err(err) => return Err(js_error(err)),
+    };
+    if !response.ok() {
+        return Err(AppError::Http(response.status()));
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN: NumberSeparators = NumberSeparators { decimal: '.', grouping: Some(',') };
+    const DE: NumberSeparators = NumberSeparators { decimal: ',', grouping: Some('.') };
+    const FR: NumberSeparators = NumberSeparators { decimal: ',', grouping: Some('\u{202f}') };
+
+    #[test]
+    fn normalize_reads_each_locale() {
+        assert_eq!(EN.normalize("1,234.5").as_deref(), Some("1234.5"));
+        assert_eq!(DE.normalize("1.234,5").as_deref(), Some("1234.5"));
+        assert_eq!(FR.normalize("1 234,5").as_deref(), Some("1234.5"));
+        assert_eq!(DE.normalize("0,25").as_deref(), Some("0.25"));
+        assert_eq!(FR.normalize("0.25").as_deref(), Some("0.25"));
+        assert_eq!(EN.normalize("").as_deref(), Some(""));
+    }
+
+    #[test]
+    fn normalize_refuses_ambiguous_grouping() {
+        assert_eq!(DE.normalize("1.2345"), None);
+        assert_eq!(DE.normalize("0.250"), None);
+        assert_eq!(DE.normalize("0.25"), None);
+        assert_eq!(EN.normalize("1,5"), None);
+        assert_eq!(EN.normalize("0,250"), None);
+        assert_eq!(EN.normalize("1,2345.6"), None);
+        assert_eq!(EN.normalize("1.23,4"), None);
+    }
+
+    #[test]
+    fn abbreviate_keeps_short_values_whole() {
+        assert_eq!(abbreviate(""), "");
+        assert_eq!(abbreviate("abcdefg"), "abcdefg");
+        assert_eq!(abbreviate("abcdefghijkl"), "abcdefghijkl");
+    }
+
+    #[test]
+    fn abbreviate_shortens_past_twelve_chars() {
+        assert_eq!(abbreviate("abcdefghijklm"), "abcdef...hijklm");
+        assert_eq!(
+            abbreviate("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"),
+            "EPjFWd...yTDt1v"
+        );
+    }
+
+    #[test]
+    fn abbreviate_counts_chars_not_bytes() {
+        // Twelve two-byte chars: shown whole, though they are 24 bytes.
+        assert_eq!(abbreviate("ééééééàààààà"), "ééééééàààààà");
+        assert_eq!(abbreviate("日本語テキストの例です。長い"), "日本語テキス...例です。長い");
+    }
+
+    #[test]
+    fn decimal_str_to_base_units_scales_by_decimals() {
+        assert_eq!(decimal_str_to_base_units("7", 0, RoundingMode::Floor), Some(7));
+        assert_eq!(decimal_str_to_base_units("1.5", 9, RoundingMode::Floor), Some(1_500_000_000));
+        assert_eq!(decimal_str_to_base_units("0.000000001", 9, RoundingMode::Floor), Some(1));
+        assert_eq!(decimal_str_to_base_units(" 2 ", 2, RoundingMode::Floor), Some(200));
+    }
+
+    #[test]
+    fn decimal_str_to_base_units_rounds_extra_digits() {
+        assert_eq!(decimal_str_to_base_units("1.5", 0, RoundingMode::Floor), Some(1));
+        assert_eq!(decimal_str_to_base_units("1.5", 0, RoundingMode::Round), Some(2));
+        assert_eq!(decimal_str_to_base_units("0.0000000014", 9, RoundingMode::Round), Some(1));
+        assert_eq!(decimal_str_to_base_units("0.0000000015", 9, RoundingMode::Floor), Some(1));
+        assert_eq!(decimal_str_to_base_units("0.0000000015", 9, RoundingMode::Ceil), Some(2));
+        assert_eq!(decimal_str_to_base_units("1.0000000000", 9, RoundingMode::Ceil), Some(1_000_000_000));
+    }
+
+    #[test]
+    fn decimal_str_to_base_units_refuses_overflow() {
+        assert_eq!(decimal_str_to_base_units("18446744073709551615", 0, RoundingMode::Floor), Some(u64::MAX));
+        assert_eq!(decimal_str_to_base_units("18446744073709551616", 0, RoundingMode::Floor), None);
+        assert_eq!(decimal_str_to_base_units("18446744073.709551615", 9, RoundingMode::Floor), Some(u64::MAX));
+        assert_eq!(decimal_str_to_base_units("18446744073.709551616", 9, RoundingMode::Floor), None);
+        assert_eq!(decimal_str_to_base_units("18446744073709551615.1", 0, RoundingMode::Ceil), None);
+    }
+
+    #[test]
+    fn decimal_str_to_base_units_takes_bare_points_and_trailing_zeros() {
+        assert_eq!(decimal_str_to_base_units(".5", 1, RoundingMode::Floor), Some(5));
+        assert_eq!(decimal_str_to_base_units("1.", 2, RoundingMode::Floor), Some(100));
+        assert_eq!(decimal_str_to_base_units("1.500000000", 9, RoundingMode::Floor), Some(1_500_000_000));
+        assert_eq!(decimal_str_to_base_units("0.10", 1, RoundingMode::Floor), Some(1));
+    }
+
+    #[test]
+    fn decimal_str_to_base_units_refuses_other_forms() {
+        for amount in ["", ".", "-1", "+1", "1e3", "1.2.3", "1,5", "abc"] {
+            assert_eq!(decimal_str_to_base_units(amount, 6, RoundingMode::Floor), None, "{:?}", amount);
+        }
+    }
+
+    #[test]
+    fn base_units_to_decimal_str_drops_trailing_zeros() {
+        assert_eq!(base_units_to_decimal_str(0, 0), "0");
+        assert_eq!(base_units_to_decimal_str(0, 9), "0");
+        assert_eq!(base_units_to_decimal_str(1, 9), "0.000000001");
+        assert_eq!(base_units_to_decimal_str(1500, 3), "1.5");
+        assert_eq!(base_units_to_decimal_str(100, 2), "1");
+        assert_eq!(base_units_to_decimal_str(u64::MAX, 0), "18446744073709551615");
+        assert_eq!(base_units_to_decimal_str(u64::MAX, 9), "18446744073.709551615");
+    }
+
+    #[test]
+    fn base_units_round_trip_through_decimal_strings() {
+        for units in [0, 1, 10, 999, 1_000_000_000, 123_456_789_012, u64::MAX] {
+            for decimals in [0, 1, 6, 9] {
+                let text = base_units_to_decimal_str(units, decimals);
+                assert_eq!(decimal_str_to_base_units(&text, decimals, RoundingMode::Floor), Some(units), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn amount_to_base_units_falls_back_to_floats() {
+        assert_eq!(amount_to_base_units("1.5", 6, RoundingMode::Floor), Some(1_500_000));
+        assert_eq!(amount_to_base_units("1e-6", 6, RoundingMode::Floor), Some(1));
+        assert_eq!(amount_to_base_units("1e3", 0, RoundingMode::Floor), Some(1000));
+        assert_eq!(amount_to_base_units("-1", 6, RoundingMode::Floor), None);
+        assert_eq!(amount_to_base_units("inf", 6, RoundingMode::Floor), None);
+        assert_eq!(amount_to_base_units("abc", 6, RoundingMode::Floor), None);
+    }
+
+    #[test]
+    fn ui_amount_to_base_units_is_exact_on_exact_amounts() {
+        for mode in RoundingMode::ALL {
+            assert_eq!(ui_amount_to_base_units(1.5, 6, mode), 1_500_000);
+            assert_eq!(ui_amount_to_base_units(0.0, 9, mode), 0);
+            // 0.29 * 100 is 28.999999999999996 in f64.
+            assert_eq!(ui_amount_to_base_units(0.29, 2, mode), 29);
+        }
+    }
+
+    #[test]
+    fn ui_amount_to_base_units_rounds_half_units_per_mode() {
+        assert_eq!(ui_amount_to_base_units(1.25, 1, RoundingMode::Floor), 12);
+        assert_eq!(ui_amount_to_base_units(1.25, 1, RoundingMode::Round), 13);
+        assert_eq!(ui_amount_to_base_units(1.25, 1, RoundingMode::Ceil), 13);
+        assert_eq!(ui_amount_to_base_units(0.5, 0, RoundingMode::Floor), 0);
+        assert_eq!(ui_amount_to_base_units(0.5, 0, RoundingMode::Round), 1);
+        assert_eq!(ui_amount_to_base_units(0.5, 0, RoundingMode::Ceil), 1);
+        assert_eq!(ui_amount_to_base_units(1.2, 0, RoundingMode::Round), 1);
+        assert_eq!(ui_amount_to_base_units(1.2, 0, RoundingMode::Ceil), 2);
+    }
+
+    #[test]
+    fn ui_amount_to_base_units_saturates_at_u64_max() {
+        for mode in RoundingMode::ALL {
+            assert_eq!(ui_amount_to_base_units(u64::MAX as f64, 0, mode), u64::MAX);
+            assert_eq!(ui_amount_to_base_units(1e30, 9, mode), u64::MAX);
+        }
+    }
+
+    #[test]
+    fn sanitize_memo_replaces_control_characters() {
+        assert_eq!(sanitize_memo("rug\u{0}pull\r\nburn").as_deref(), Some("rug pull burn"));
+        assert_eq!(sanitize_memo("  tabs\t\tand   runs  ").as_deref(), Some("tabs and runs"));
+        assert_eq!(sanitize_memo("bell\u{7}\u{1b}[31mred").as_deref(), Some("bell [31mred"));
+    }
+
+    #[test]
+    fn sanitize_memo_caps_chars_and_bytes() {
+        let ascii = sanitize_memo(&"a".repeat(MAX_MEMO_CHARS + 50)).unwrap();
+        assert_eq!(ascii.chars().count(), MAX_MEMO_CHARS);
+
+        // Three bytes each: the byte cap cuts first, on a char boundary.
+        let wide = sanitize_memo(&"火".repeat(MAX_MEMO_CHARS)).unwrap();
+        assert!(wide.len() <= MAX_MEMO_BYTES);
+        assert_eq!(wide.chars().count(), MAX_MEMO_BYTES / 3);
+        assert!(wide.chars().all(|c| c == '火'));
+
+        let emoji = sanitize_memo(&"🔥".repeat(100)).unwrap();
+        assert_eq!(emoji.len(), MAX_MEMO_BYTES);
+    }
+
+    #[test]
+    fn sanitize_memo_drops_empty_memos() {
+        assert_eq!(sanitize_memo(""), None);
+        assert_eq!(sanitize_memo("   "), None);
+        assert_eq!(sanitize_memo("\n\t\u{0}"), None);
+    }
+
+    #[test]
+    fn is_near_full_supply_includes_the_threshold() {
+        assert!(is_near_full_supply(50, 100, 50.0));
+        assert!(!is_near_full_supply(49, 100, 50.0));
+        assert!(is_near_full_supply(100, 100, 100.0));
+        assert!(!is_near_full_supply(99, 100, 100.0));
+        assert!(is_near_full_supply(u64::MAX, u64::MAX, 90.0));
+    }
+
+    #[test]
+    fn is_near_full_supply_is_false_at_zero_supply() {
+        assert!(!is_near_full_supply(0, 0, 90.0));
+        assert!(!is_near_full_supply(1, 0, 0.0));
+    }
+
+    #[test]
+    fn amount_diverges_allows_only_rounding() {
+        assert!(!amount_diverges("1.5", 1_500_000, 6));
+        assert!(amount_diverges("1.5", 1_500_001, 6));
+        assert!(amount_diverges("1.5", 1_499_999, 6));
+        // Between one and two base units: either end is fine, nothing past.
+        assert!(!amount_diverges("0.0000015", 1, 6));
+        assert!(!amount_diverges("0.0000015", 2, 6));
+        assert!(amount_diverges("0.0000015", 0, 6));
+        assert!(amount_diverges("0.0000015", 3, 6));
+    }
+
+    #[test]
+    fn amount_diverges_allows_one_unit_for_float_forms() {
+        assert!(!amount_diverges("1e-6", 1, 6));
+        assert!(!amount_diverges("1e-6", 2, 6));
+        assert!(amount_diverges("1e-6", 3, 6));
+        assert!(amount_diverges("abc", 0, 6));
+    }
+
+    #[test]
+    fn is_below_smallest_unit_at_the_boundary() {
+        assert!(!is_below_smallest_unit("0.000001", 6));
+        assert!(is_below_smallest_unit("0.0000009", 6));
+        assert!(is_below_smallest_unit("1e-7", 6));
+        assert!(is_below_smallest_unit("0.5", 0));
+        assert!(!is_below_smallest_unit("1", 0));
+        assert!(!is_below_smallest_unit("0", 6));
+        assert!(!is_below_smallest_unit("abc", 6));
+    }
+
+    #[test]
+    fn from_sample_reads_each_locale() {
+        assert_eq!(NumberSeparators::from_sample("12,345.6"), EN);
+        assert_eq!(NumberSeparators::from_sample("12.345,6"), DE);
+        assert_eq!(NumberSeparators::from_sample("12\u{202f}345,6"), FR);
+        assert_eq!(
+            NumberSeparators::from_sample("12\u{a0}345,6"),
+            NumberSeparators { decimal: ',', grouping: Some('\u{a0}') }
+        );
+    }
+
+    #[test]
+    fn from_sample_without_grouping() {
+        assert_eq!(NumberSeparators::from_sample("12345.6"), NumberSeparators { decimal: '.', grouping: None });
+        assert_eq!(NumberSeparators::from_sample("12345,6"), NumberSeparators { decimal: ',', grouping: None });
+    }
+
+    #[test]
+    fn from_sample_falls_back_to_the_default() {
+        assert_eq!(NumberSeparators::from_sample(""), NumberSeparators::default());
+        assert_eq!(NumberSeparators::from_sample("7"), NumberSeparators::default());
+        assert_eq!(NumberSeparators::from_sample("123456"), NumberSeparators::default());
+    }
+
+    const SECOND: f64 = 1_000.0;
+    const NOW: f64 = 1_700_000_000_000.0;
+
+    #[test]
+    fn relative_time_reads_just_now_for_recent_or_future_timestamps() {
+        assert_eq!(format_relative_time(NOW, NOW), "just now");
+        assert_eq!(format_relative_time(NOW - 9.0 * SECOND, NOW), "just now");
+        assert_eq!(format_relative_time(NOW + 60.0 * SECOND, NOW), "just now");
+        assert_eq!(format_relative_time(f64::NAN, NOW), "just now");
+    }
+
+    #[test]
+    fn relative_time_picks_the_largest_unit() {
+        assert_eq!(format_relative_time(NOW - 10.0 * SECOND, NOW), "10 seconds ago");
+        assert_eq!(format_relative_time(NOW - 60.0 * SECOND, NOW), "1 minute ago");
+        assert_eq!(format_relative_time(NOW - 119.0 * SECOND, NOW), "1 minute ago");
+        assert_eq!(format_relative_time(NOW - 2.0 * 3_600.0 * SECOND, NOW), "2 hours ago");
+        assert_eq!(format_relative_time(NOW - 86_400.0 * SECOND, NOW), "1 day ago");
+        assert_eq!(format_relative_time(NOW - 45.0 * 86_400.0 * SECOND, NOW), "45 days ago");
+    }
+
+    #[test]
+    fn is_below_smallest_unit_across_decimals() {
+        for (decimals, below, smallest) in [
+            (0, "0.9", "1"),
+            (2, "0.009", "0.01"),
+            (6, "0.0000009", "0.000001"),
+            (9, "0.0000000009", "0.000000001"),
+            (12, "0.0000000000009", "0.000000000001"),
+        ] {
+            assert!(is_below_smallest_unit(below, decimals), "{} at {} decimals", below, decimals);
+            assert!(!is_below_smallest_unit(smallest, decimals), "{} at {} decimals", smallest, decimals);
+        }
+    }
+
+
+    #[test]
+    fn priority_fee_rounds_up_to_whole_lamports() {
+        assert_eq!(PriorityFee::None.lamports(20_000), 0);
+        assert_eq!(PriorityFee::Normal.lamports(20_000), 200);
+        assert_eq!(PriorityFee::Custom(1).lamports(1), 1);
+        assert_eq!(PriorityFee::Custom(1).lamports(1_000_001), 2);
+    }
+
+    #[test]
+    fn priority_fee_saturates_instead_of_overflowing() {
+        assert_eq!(PriorityFee::Custom(u64::MAX).lamports(u64::MAX), u64::MAX);
+        assert_eq!(PriorityFee::Custom(u64::MAX).lamports(1_400_000), u64::MAX);
+        assert_eq!(PriorityFee::Custom(u64::MAX / 2).lamports(2), (u64::MAX - 1).div_ceil(1_000_000));
+    }
+
+    #[test]
+    fn custom_priority_fee_is_capped() {
+        assert_eq!(PriorityFee::custom("5000"), PriorityFee::Custom(5_000));
+        assert_eq!(PriorityFee::custom(" 5000 "), PriorityFee::Custom(5_000));
+        assert_eq!(PriorityFee::custom("999999999999"), PriorityFee::Custom(MAX_CUSTOM_PRIORITY_FEE));
+        assert_eq!(PriorityFee::custom(&"9".repeat(40)), PriorityFee::Custom(MAX_CUSTOM_PRIORITY_FEE));
+        assert_eq!(PriorityFee::custom(""), PriorityFee::Custom(0));
+        assert_eq!(PriorityFee::custom("-5"), PriorityFee::Custom(0));
+        assert_eq!(PriorityFee::custom("1.5"), PriorityFee::Custom(0));
+    }
+
+
+    const SIGNATURE: &str = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+
+    #[test]
+    fn explorer_url_for_each_cluster_and_explorer() {
+        let cases = [
+            (Explorer::SolanaExplorer, Cluster::Mainnet, "https://explorer.solana.com/tx/{}"),
+            (Explorer::SolanaExplorer, Cluster::Devnet, "https://explorer.solana.com/tx/{}?cluster=devnet"),
+            (Explorer::SolanaExplorer, Cluster::Testnet, "https://explorer.solana.com/tx/{}?cluster=testnet"),
+            (Explorer::Solscan, Cluster::Mainnet, "https://solscan.io/tx/{}"),
+            (Explorer::Solscan, Cluster::Devnet, "https://solscan.io/tx/{}?cluster=devnet"),
+            (Explorer::Solscan, Cluster::Testnet, "https://solscan.io/tx/{}?cluster=testnet"),
+            (Explorer::SolanaFm, Cluster::Mainnet, "https://solana.fm/tx/{}?cluster=mainnet-alpha"),
+            (Explorer::SolanaFm, Cluster::Devnet, "https://solana.fm/tx/{}?cluster=devnet-solana"),
+            (Explorer::SolanaFm, Cluster::Testnet, "https://solana.fm/tx/{}?cluster=testnet-solana"),
+        ];
+        for (explorer, cluster, expected) in cases {
+            assert_eq!(explorer_url(SIGNATURE, cluster, explorer), expected.replace("{}", SIGNATURE));
+        }
+    }
+
+
+    const ADDRESS: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    #[test]
+    fn explorer_address_url_for_each_cluster_and_explorer() {
+        let cases = [
+            (Explorer::SolanaExplorer, Cluster::Mainnet, "https://explorer.solana.com/address/{}"),
+            (Explorer::SolanaExplorer, Cluster::Devnet, "https://explorer.solana.com/address/{}?cluster=devnet"),
+            (Explorer::SolanaExplorer, Cluster::Testnet, "https://explorer.solana.com/address/{}?cluster=testnet"),
+            (Explorer::Solscan, Cluster::Mainnet, "https://solscan.io/account/{}"),
+            (Explorer::Solscan, Cluster::Devnet, "https://solscan.io/account/{}?cluster=devnet"),
+            (Explorer::Solscan, Cluster::Testnet, "https://solscan.io/account/{}?cluster=testnet"),
+            (Explorer::SolanaFm, Cluster::Mainnet, "https://solana.fm/address/{}?cluster=mainnet-alpha"),
+            (Explorer::SolanaFm, Cluster::Devnet, "https://solana.fm/address/{}?cluster=devnet-solana"),
+            (Explorer::SolanaFm, Cluster::Testnet, "https://solana.fm/address/{}?cluster=testnet-solana"),
+        ];
+        for (explorer, cluster, expected) in cases {
+            assert_eq!(explorer_address_url(ADDRESS, cluster, explorer), expected.replace("{}", ADDRESS));
+        }
+    }
+
+    #[test]
+    fn solana_fm_names_every_cluster() {
+        assert_eq!(Explorer::SolanaFm.cluster_param(Cluster::Mainnet), Some("mainnet-alpha"));
+        assert_eq!(Explorer::SolanaFm.cluster_param(Cluster::Devnet), Some("devnet-solana"));
+        assert_eq!(Explorer::SolanaFm.cluster_param(Cluster::Testnet), Some("testnet-solana"));
+        for explorer in [Explorer::SolanaExplorer, Explorer::Solscan] {
+            assert_eq!(explorer.cluster_param(Cluster::Mainnet), None);
+            assert_eq!(explorer.cluster_param(Cluster::Devnet), Some("devnet"));
+        }
+    }
+
+    #[test]
+    fn explorer_names_round_trip() {
+        for explorer in Explorer::ALL {
+            assert_eq!(Explorer::from_name(explorer.name()), Some(explorer));
+        }
+        assert_eq!(Explorer::from_name("etherscan"), None);
+    }
+
+
+    #[test]
+    fn limit_decimal_places_without_decimals() {
+        assert_eq!(limit_decimal_places("12", 0), ("12".to_string(), false));
+        assert_eq!(limit_decimal_places("12.", 0), ("12".to_string(), true));
+        assert_eq!(limit_decimal_places("12.5", 0), ("12".to_string(), true));
+    }
+
+    #[test]
+    fn limit_decimal_places_at_two_six_and_nine() {
+        assert_eq!(limit_decimal_places("1.", 2), ("1.".to_string(), false));
+        assert_eq!(limit_decimal_places("1.23", 2), ("1.23".to_string(), false));
+        assert_eq!(limit_decimal_places("1.234", 2), ("1.23".to_string(), true));
+        assert_eq!(limit_decimal_places("0.123456", 6), ("0.123456".to_string(), false));
+        assert_eq!(limit_decimal_places("0.1234567", 6), ("0.123456".to_string(), true));
+        assert_eq!(limit_decimal_places("0.123456789", 9), ("0.123456789".to_string(), false));
+        assert_eq!(limit_decimal_places("0.1234567891", 9), ("0.123456789".to_string(), true));
+    }
+
+    #[test]
+    fn limit_decimal_places_leaves_other_forms_alone() {
+        assert_eq!(limit_decimal_places("1e-7", 6), ("1e-7".to_string(), false));
+        assert_eq!(limit_decimal_places("1.5e3", 0), ("1.5e3".to_string(), false));
+        assert_eq!(limit_decimal_places("", 6), (String::new(), false));
+    }
+
+    #[test]
+    fn cluster_rpc_url_for_each_cluster() {
+        assert_eq!(cluster_rpc_url(Cluster::Mainnet), "https://api.mainnet-beta.solana.com");
+        assert_eq!(cluster_rpc_url(Cluster::Devnet), "https://api.devnet.solana.com");
+        assert_eq!(cluster_rpc_url(Cluster::Testnet), "https://api.testnet.solana.com");
+    }
+
+    #[test]
+    fn truncate_middle_leaves_short_values_alone() {
+        assert_eq!(truncate_middle("abc", 5), "abc");
+        assert_eq!(truncate_middle("abcde", 5), "abcde");
+    }
+
+    #[test]
+    fn truncate_middle_keeps_both_ends_of_long_values() {
+        assert_eq!(truncate_middle("abcdefghij", 5), "ab…ij");
+        assert_eq!(truncate_middle("abcdefghij", 6), "abc…ij");
+    }
+
+    #[test]
+    fn truncate_middle_caps_very_long_strings() {
+        let hostile = format!("{}{}", "A".repeat(10_000), "Z");
+        let shown = truncate_middle(&hostile, MAX_ADDRESS_LEN);
+        assert_eq!(shown.chars().count(), MAX_ADDRESS_LEN);
+        assert!(shown.starts_with('A') && shown.ends_with('Z'));
+
+        let wide = "🔥".repeat(10_000);
+        assert_eq!(truncate_middle(&wide, MAX_SIGNATURE_LEN).chars().count(), MAX_SIGNATURE_LEN);
+    }
+
+    #[test]
+    fn very_long_addresses_and_signatures_are_invalid() {
+        assert!(is_valid_address(&"1".repeat(MAX_ADDRESS_LEN)));
+        assert!(!is_valid_address(&"1".repeat(MAX_ADDRESS_LEN + 1)));
+        assert!(!is_valid_mint(&"1".repeat(10_000)));
+        assert!(is_valid_signature(&"1".repeat(MAX_SIGNATURE_LEN)));
+        assert!(!is_valid_signature(&"1".repeat(MAX_SIGNATURE_LEN + 1)));
+        assert!(!is_valid_signature(&"1".repeat(10_000)));
+    }
+
+    #[test]
+    fn format_amount_rounds_float_noise_to_the_decimals() {
+        assert_eq!(format_amount(0.1 + 0.2, 6), "0.3");
+        assert_eq!(format_amount(99.99999999999999, 2), "100");
+        assert_eq!(format_amount(2.0, 0), "2");
+        assert_eq!(format_amount(0.000_001, 6), "0.000001");
+    }
+}
@@ -0,0 +1,327 @@
+use crate::transaction;
+use crate::utils::{amount_to_base_units, is_below_smallest_unit, is_valid_mint, RoundingMode, MAX_ADDRESS_LEN};
+
+/// What the burn form knows when deciding whether a burn can go ahead.
+pub struct BurnCheck<'a> {
+    pub connected: bool,
+    pub busy: bool,
+    pub maintenance: bool,
+    pub mint: &'a str,
+    pub compressed_asset: bool,
+    /// The mint's decimals and token program are both known.
+    pub token_details_loaded: bool,
+    /// `None` while the token account lookup is in flight.
+    pub holds_token: Option<bool>,
+    /// Balance of the token account, in base units, once looked up.
+    pub balance: Option<u64>,
+    pub frozen: bool,
+    pub lacks_fee_sol: bool,
+    /// An earlier burn of this mint hasn't resolved yet.
+    pub already_in_flight: bool,
+    /// The typed amount's separators could be read more than one way; see
+    /// `NumberSeparators::normalize`.
+    pub amount_ambiguous: bool,
+    /// Canonical dot-decimal form; empty when ambiguous.
+    pub amount: &'a str,
+    pub decimals: Option<u8>,
+    pub rounding: RoundingMode,
+}
+
+impl BurnCheck<'_> {
+    /// Unwrapping closes the account and takes the whole balance, so there is
+    /// no amount to check.
+    fn unwrap(&self) -> bool {
+        transaction::is_wrapped_sol(self.mint)
+    }
+}
+
+/// Why a burn can't go ahead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AmountError {
+    Disconnected,
+    Busy,
+    Maintenance,
+    MintMissing,
+    MintTooLong,
+    Compressed,
+    MintInvalid,
+    TokenDetailsLoading,
+    BalanceLoading,
+    NotHeld,
+    Frozen,
+    InsufficientFeeSol,
+    AlreadyInFlight,
+    AmountAmbiguous,
+    AmountMissing,
+    AmountInvalid,
+    AmountNotPositive,
+    BelowSmallestUnit,
+    ExceedsBalance,
+}
+
+impl AmountError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            AmountError::Disconnected => "Connect your wallet to burn.",
+            AmountError::Busy => "Waiting for the current burn to finish.",
+            AmountError::Maintenance => "Burning is disabled during maintenance.",
+            AmountError::MintMissing => "Enter the mint address of the token to burn.",
+            AmountError::MintTooLong => "The mint address is too long.",
+            AmountError::Compressed => "Compressed NFTs can't be burned here.",
+            AmountError::MintInvalid => "The mint address isn't valid.",
+            AmountError::TokenDetailsLoading => "Loading this token's details…",
+            AmountError::BalanceLoading => "Checking your balance of this token…",
+            AmountError::NotHeld => "You don't hold this token.",
+            AmountError::Frozen => "This token account is frozen.",
+            AmountError::InsufficientFeeSol => "Not enough SOL to pay the transaction fee.",
+            AmountError::AlreadyInFlight => {
+                "A burn of this token is still awaiting confirmation. Wait for it before burning again."
+            }
+            AmountError::AmountAmbiguous => {
+                "The amount's separators are ambiguous. Group digits in threes, or leave the grouping out."
+            }
+            AmountError::AmountMissing => "Enter an amount to burn.",
+            AmountError::AmountInvalid => "The amount isn't a valid number.",
+            AmountError::AmountNotPositive => "The amount must be greater than 0.",
+            AmountError::BelowSmallestUnit => "Amount is below the smallest burnable unit.",
+            AmountError::ExceedsBalance => "The amount is more than your balance.",
+        }
+    }
+}
+
+type Validator = fn(&BurnCheck) -> Result<(), AmountError>;
+
+/// Every rule, in the order they're reported: the first failure is the one
+/// the user sees, so earlier rules are the ones to fix first.
+const PIPELINE: &[Validator] = &[
+    connected,
+    idle,
+    not_in_maintenance,
+    mint_present,
+    mint_length,
+    not_compressed,
+    mint_valid,
+    token_details_loaded,
+    balance_known,
+    token_held,
+    not_frozen,
+    fee_covered,
+    not_in_flight,
+    amount_unambiguous,
+    amount_present,
+    amount_number,
+    amount_positive,
+    amount_burnable,
+    amount_within_balance,
+];
+
+/// The first rule `check` breaks, if any.
+pub fn validate(check: &BurnCheck) -> Result<(), AmountError> {
+    PIPELINE.iter().try_for_each(|validator| validator(check))
+}
+
+fn require(ok: bool, error: AmountError) -> Result<(), AmountError> {
+    if ok {
+        Ok(())
+    } else {
+        Err(error)
+    }
+}
+
+fn connected(check: &BurnCheck) -> Result<(), AmountError> {
+    require(check.connected, AmountError::Disconnected)
+}
+
+fn idle(check: &BurnCheck) -> Result<(), AmountError> {
+    require(!check.busy, AmountError::Busy)
+}
+
+fn not_in_maintenance(check: &BurnCheck) -> Result<(), AmountError> {
+    require(!check.maintenance, AmountError::Maintenance)
+}
+
+fn mint_present(check: &BurnCheck) -> Result<(), AmountError> {
+    require(!check.mint.is_empty(), AmountError::MintMissing)
+}
+
+fn mint_length(check: &BurnCheck) -> Result<(), AmountError> {
+    require(check.mint.len() <= MAX_ADDRESS_LEN, AmountError::MintTooLong)
+}
+
+fn not_compressed(check: &BurnCheck) -> Result<(), AmountError> {
+    require(!check.compressed_asset, AmountError::Compressed)
+}
+
+fn mint_valid(check: &BurnCheck) -> Result<(), AmountError> {
+    require(is_valid_mint(check.mint), AmountError::MintInvalid)
+}
+
+fn token_details_loaded(check: &BurnCheck) -> Result<(), AmountError> {
+    require(check.token_details_loaded, AmountError::TokenDetailsLoading)
+}
+
+fn balance_known(check: &BurnCheck) -> Result<(), AmountError> {
+    require(check.holds_token.is_some(), AmountError::BalanceLoading)
+}
+
+fn token_held(check: &BurnCheck) -> Result<(), AmountError> {
+    require(check.holds_token != Some(false), AmountError::NotHeld)
+}
+
+fn not_frozen(check: &BurnCheck) -> Result<(), AmountError> {
+    require(!check.frozen, AmountError::Frozen)
+}
+
+fn fee_covered(check: &BurnCheck) -> Result<(), AmountError> {
+    require(!check.lacks_fee_sol, AmountError::InsufficientFeeSol)
+}
+
+fn not_in_flight(check: &BurnCheck) -> Result<(), AmountError> {
+    require(!check.already_in_flight, AmountError::AlreadyInFlight)
+}
+
+fn amount_unambiguous(check: &BurnCheck) -> Result<(), AmountError> {
+    require(check.unwrap() || !check.amount_ambiguous, AmountError::AmountAmbiguous)
+}
+
+fn amount_present(check: &BurnCheck) -> Result<(), AmountError> {
+    require(check.unwrap() || !check.amount.trim().is_empty(), AmountError::AmountMissing)
+}
+
+fn amount_number(check: &BurnCheck) -> Result<(), AmountError> {
+    require(check.unwrap() || check.amount.trim().parse::<f64>().is_ok(), AmountError::AmountInvalid)
+}
+
+fn amount_positive(check: &BurnCheck) -> Result<(), AmountError> {
+    let positive = check.amount.trim().parse::<f64>().map_or(false, |amount| amount > 0.0);
+    require(check.unwrap() || positive, AmountError::AmountNotPositive)
+}
+
+/// The decimals are known by now; `token_details_loaded` comes first.
+fn amount_burnable(check: &BurnCheck) -> Result<(), AmountError> {
+    let below = check.decimals.map_or(false, |decimals| is_below_smallest_unit(check.amount, decimals));
+    require(check.unwrap() || !below, AmountError::BelowSmallestUnit)
+}
+
+/// Compared in base units, rounded the way the burn will be, so Max and
+/// amounts that round down onto the balance pass. An amount too large to
+/// convert at all is over any balance.
+fn amount_within_balance(check: &BurnCheck) -> Result<(), AmountError> {
+    let over = match (check.balance, check.decimals) {
+        (Some(balance), Some(decimals)) => amount_to_base_units(check.amount, decimals, check.rounding)
+            .map_or(true, |units| units > balance),
+        _ => false,
+    };
+    require(check.unwrap() || !over, AmountError::ExceedsBalance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::WSOL_MINT;
+
+    const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    /// A check every rule passes: 1.5 of a 6-decimal token, out of 2.
+    fn ready() -> BurnCheck<'static> {
+        BurnCheck {
+            connected: true,
+            busy: false,
+            maintenance: false,
+            mint: MINT,
+            compressed_asset: false,
+            token_details_loaded: true,
+            holds_token: Some(true),
+            balance: Some(2_000_000),
+            frozen: false,
+            lacks_fee_sol: false,
+            already_in_flight: false,
+            amount_ambiguous: false,
+            amount: "1.5",
+            decimals: Some(6),
+            rounding: RoundingMode::Floor,
+        }
+    }
+
+    fn fails(check: BurnCheck, error: AmountError) {
+        assert_eq!(validate(&check), Err(error));
+    }
+
+    #[test]
+    fn ready_passes() {
+        assert_eq!(validate(&ready()), Ok(()));
+    }
+
+    #[test]
+    fn each_rule_reports_its_error() {
+        fails(BurnCheck { connected: false, ..ready() }, AmountError::Disconnected);
+        fails(BurnCheck { busy: true, ..ready() }, AmountError::Busy);
+        fails(BurnCheck { maintenance: true, ..ready() }, AmountError::Maintenance);
+        fails(BurnCheck { mint: "", ..ready() }, AmountError::MintMissing);
+        fails(BurnCheck { mint: &"1".repeat(MAX_ADDRESS_LEN + 1), ..ready() }, AmountError::MintTooLong);
+        fails(BurnCheck { compressed_asset: true, ..ready() }, AmountError::Compressed);
+        fails(BurnCheck { mint: "not-a-mint", ..ready() }, AmountError::MintInvalid);
+        fails(BurnCheck { token_details_loaded: false, ..ready() }, AmountError::TokenDetailsLoading);
+        fails(BurnCheck { holds_token: None, ..ready() }, AmountError::BalanceLoading);
+        fails(BurnCheck { holds_token: Some(false), ..ready() }, AmountError::NotHeld);
+        fails(BurnCheck { frozen: true, ..ready() }, AmountError::Frozen);
+        fails(BurnCheck { lacks_fee_sol: true, ..ready() }, AmountError::InsufficientFeeSol);
+        fails(BurnCheck { already_in_flight: true, ..ready() }, AmountError::AlreadyInFlight);
+        fails(BurnCheck { amount_ambiguous: true, amount: "", ..ready() }, AmountError::AmountAmbiguous);
+        fails(BurnCheck { amount: " ", ..ready() }, AmountError::AmountMissing);
+        fails(BurnCheck { amount: "1.5x", ..ready() }, AmountError::AmountInvalid);
+        fails(BurnCheck { amount: "0", ..ready() }, AmountError::AmountNotPositive);
+        fails(BurnCheck { amount: "-1", ..ready() }, AmountError::AmountNotPositive);
+        fails(BurnCheck { amount: "0.0000001", ..ready() }, AmountError::BelowSmallestUnit);
+        fails(BurnCheck { amount: "2.000001", ..ready() }, AmountError::ExceedsBalance);
+    }
+
+    #[test]
+    fn earlier_rules_win() {
+        fails(BurnCheck { connected: false, busy: true, amount: "", ..ready() }, AmountError::Disconnected);
+        fails(BurnCheck { frozen: true, amount: "0", ..ready() }, AmountError::Frozen);
+    }
+
+    #[test]
+    fn balance_is_compared_in_rounded_base_units() {
+        assert_eq!(validate(&BurnCheck { amount: "2", ..ready() }), Ok(()));
+        assert_eq!(validate(&BurnCheck { amount: "2.0000004", ..ready() }), Ok(()));
+        fails(
+            BurnCheck { amount: "2.0000004", rounding: RoundingMode::Ceil, ..ready() },
+            AmountError::ExceedsBalance,
+        );
+        fails(BurnCheck { amount: "99999999999999999999", ..ready() }, AmountError::ExceedsBalance);
+        assert_eq!(validate(&BurnCheck { balance: None, holds_token: Some(true), amount: "5", ..ready() }), Ok(()));
+    }
+
+    #[test]
+    fn unwrapping_skips_the_amount_rules() {
+        assert_eq!(validate(&BurnCheck { mint: WSOL_MINT, amount: "", ..ready() }), Ok(()));
+        assert_eq!(validate(&BurnCheck { mint: WSOL_MINT, amount: "9", ..ready() }), Ok(()));
+    }
+
+    #[test]
+    fn sub_unit_message_matches_the_requested_wording() {
+        assert_eq!(AmountError::BelowSmallestUnit.message(), "Amount is below the smallest burnable unit.");
+    }
+
+    #[test]
+    fn sub_unit_amounts_fail_across_decimals() {
+        for (decimals, below, smallest) in [
+            (0, "0.5", "1"),
+            (2, "0.001", "0.01"),
+            (6, "0.0000001", "0.000001"),
+            (9, "0.0000000001", "0.000000001"),
+        ] {
+            let check = |amount| BurnCheck { amount, decimals: Some(decimals), balance: Some(u64::MAX), ..ready() };
+            fails(check(below), AmountError::BelowSmallestUnit);
+            assert_eq!(validate(&check(smallest)), Ok(()), "{} at {} decimals", smallest, decimals);
+        }
+    }
+
+    #[test]
+    fn pathologically_long_mints_are_rejected() {
+        let hostile = "1".repeat(100_000);
+        fails(BurnCheck { mint: &hostile, ..ready() }, AmountError::MintTooLong);
+    }
+}